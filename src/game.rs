@@ -14,49 +14,199 @@ extern crate rand;          // génération de nombres aléatoires.
 extern crate serde;         // sérialisation et la désérialisation de données.
 extern crate serde_json;    // travailler avec le format JSON.
 
-use std::collections::HashSet;
-use rand::Rng;
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::process::ExitCode;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::time::{Duration, Instant};
 
 use serde_derive::{Deserialize, Serialize};
 
+use clues::ClueRule;
+use grid::Grid;
+use mask::MaskSource;
+use neighbourhood::{Neighbourhood, Topology};
+use results::{GameResult, Outcome};
+use command::{Command, Notation, Position};
+use save::SavedGame;
+
+mod args;
+mod autoplay;
+mod bench;
+mod clues;
+mod command;
 mod display;
+mod endless;
+mod grid;
+mod mask;
+mod neighbourhood;
+mod protocol;
 mod read;
+mod results;
+mod save;
+mod script;
+mod simulation;
+mod solver;
+
+/*
+ *  Définition des constantes
+ */
+// nombre de cases parcourues par le curseur à chaque défilement ('w', 'a', 's', 'd').
+const SCROLL_STEP: isize = 10;
+// plus grand chiffre affichable (sur deux caractères).
+const MAX_CLUE: usize = 99;
+// nombre maximal de plateaux tirés pour en trouver un qui se résout sans deviner.
+const MAX_NO_GUESS_ATTEMPTS: usize = 200;
 
 /*
  *  Définition des Structures et des Énumérations
  */
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct BoardInfo {
+    pub(crate) name : String,
     pub(crate) board_size : usize,
     pub(crate) num_mines : usize,
+    pub(crate) layers : usize,
+    pub(crate) topology : Topology,
+    pub(crate) neighbourhood : Neighbourhood,
+    // cases jouables du plateau (de chaque couche), si sa forme n'est pas un carré plein.
+    pub(crate) mask : Option<Vec<bool>>,
+    // nombre maximal de mines d'une même case (1 pour le jeu classique).
+    pub(crate) mines_per_cell : u8,
+    // règle d'affichage des chiffres et voisinage qu'ils comptent, s'il diffère de celui des cases.
+    pub(crate) clue_rule : ClueRule,
+    pub(crate) clue_neighbourhood : Option<Neighbourhood>,
+    // plateau tiré de façon à pouvoir être résolu sans jamais deviner.
+    pub(crate) no_guess : bool,
+    // nombre de mines de chaque ligne et de chaque colonne affiché dans les marges.
+    pub(crate) line_totals : bool,
+    // nombre de mines que le joueur peut toucher avant de perdre (1 pour le jeu classique).
+    pub(crate) lives : usize,
+    // temps imparti pour déminer le plateau (compte à rebours), s'il y en a un.
+    pub(crate) time_limit : Option<Duration>,
+    // mode de mémoire : les chiffres révélés s'effacent après ce temps ou ce nombre de coups.
+    pub(crate) fade_after : Option<Duration>,
+    pub(crate) fade_moves : Option<usize>,
+    // troisième état du cycle des drapeaux : un point d'interrogation.
+    pub(crate) question_marks : bool,
 }
 
 pub struct Minesweeper {
-    board: Vec<Vec<Cell>>,
-    mines: HashSet<(usize, usize)>,
-    pub(crate) revealed: HashSet<(usize, usize)>,
+    pub(crate) grid: Grid,
+    pub(crate) num_revealed: usize,
     pub(crate) game_over: bool,
     pub(crate) num_mark: usize,
+    pub(crate) cursor: (usize, usize),
     pub(crate) player_message: Option<String>,
+    // nombre de mines touchées sans perdre la partie (mode avec plusieurs vies).
+    pub(crate) lives_lost: usize,
+    // nombre de coups joués, et instant et coup de révélation de chaque case (mode de mémoire seulement).
+    pub(crate) moves: usize,
+    revealed_at: Vec<Option<(Instant, usize)>>,
+    // coups joués depuis le début de la partie, rejoués pour annuler le dernier ou reprendre une sauvegarde.
+    pub(crate) actions: Vec<Action>,
+    // façon d'écrire les cases, choisie par le joueur (colonnes en chiffres ou en lettres).
+    pub(crate) notation: Notation,
+    // partie où le joueur s'est fait aider (robot, indice) : elle ne compte pas comme un record.
+    pub(crate) assisted: bool,
     first_play : bool,
     configuration : BoardInfo,
+    pub(crate) seed : u64,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Cell {
-    Mine,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Cell {
+    // nombre de mines de la case.
+    Mine(u8),
+    // chiffre révélé puis effacé (mode de mémoire).
+    Faded,
     Number(u8),
     Empty,
     Undiscovered,
-    Mark,
+    // nombre de drapeaux posés sur la case.
+    Mark(u8),
+    // case annotée d'un point d'interrogation.
+    Question,
+    // en fin de partie : drapeau posé sur une case sans mine (ou trop de drapeaux), et mine touchée.
+    WrongMark,
+    Exploded,
+    // trou du masque, hors du plateau.
+    Hole,
+}
+
+// Action de jeu, commune au joueur et au robot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Action {
+    Reveal(usize, usize),
+    Mark(usize, usize),
+    // révèle les voisines d'un chiffre entouré d'autant de drapeaux que de mines.
+    Chord(usize, usize),
 }
+
+// Suite choisie par le joueur en quittant une partie (ou à sa fin, où on lui demande).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NextStep {
+    Ask,
+    Quit,
+    Menu,
+    // nouveau plateau du même mode, ou même plateau (même graine, et l'aide reçue sur ce plateau).
+    Restart,
+    Retry(u64, bool),
+}
+
 #[derive(Serialize, Deserialize)]
 struct GameMode {
     name: String,
     board_size: u32,
     num_mines: u32,
     index: u32,
+    // mode infini : `board_size` est alors la taille d'un morceau et `num_mines` ses mines.
+    #[serde(default)]
+    endless: bool,
+    // forme des cases ("square" par défaut, "hex" ou "torus" pour un plateau dont les bords se rejoignent).
+    #[serde(default)]
+    topology: Topology,
+    // voisinage personnalisé (liste de décalages [ligne, colonne]), à la place de celui de la forme.
+    #[serde(default)]
+    neighbourhood: Option<Vec<(isize, isize)>>,
+    // nombre de couches d'un plateau en 3D (1 par défaut, pour un plateau plat).
+    #[serde(default)]
+    depth: u32,
+    // forme du plateau : dessin ASCII en ligne (liste de lignes) ou chemin d'un fichier.
+    #[serde(default)]
+    mask: Option<MaskSource>,
+    // nombre maximal de mines d'une même case (1 par défaut).
+    #[serde(default)]
+    mines_per_cell: u8,
+    // règle d'affichage des chiffres ("exact" par défaut, "liar" pour un chiffre faux d'un, "parity").
+    #[serde(default)]
+    clues: ClueRule,
+    // voisinage compté par les chiffres (liste de décalages [ligne, colonne]), s'il diffère de celui des cases.
+    #[serde(default)]
+    clue_neighbourhood: Option<Vec<(isize, isize)>>,
+    // plateau qui se résout sans deviner.
+    #[serde(default)]
+    no_guess: bool,
+    // nombre de mines de chaque ligne et de chaque colonne donné au joueur.
+    #[serde(default)]
+    line_totals: bool,
+    // nombre de vies du joueur (1 par défaut).
+    #[serde(default)]
+    lives: u32,
+    // compte à rebours : secondes pour déminer le plateau (0 par défaut, sans limite).
+    #[serde(default)]
+    time_limit: u32,
+    // contre-la-montre : minutes pour déminer le plus de plateaux possible (0 par défaut, une seule partie).
+    #[serde(default)]
+    time_attack: u32,
+    // mode de mémoire : secondes et coups après lesquels un chiffre révélé s'efface (0 par défaut, jamais).
+    #[serde(default)]
+    fade_seconds: u32,
+    #[serde(default)]
+    fade_moves: u32,
+    // cycle des drapeaux avec un point d'interrogation (drapeau, '?', rien).
+    #[serde(default)]
+    question_marks: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -64,13 +214,43 @@ pub struct Config {
     game_modes: Vec<GameMode>,
 }
 
+/*
+ *  Implémentation de la structure BoardInfo
+ */
+impl BoardInfo {
+    /*
+     *  Fonction qui décrit un plateau classique : carré, plat, une mine par case et sans variante
+     */
+    pub(crate) fn classic(board_size: usize, num_mines: usize) -> Self {
+        BoardInfo {
+            name: "Classic".to_string(),
+            board_size,
+            num_mines,
+            layers: 1,
+            topology: Topology::Square,
+            neighbourhood: Neighbourhood::of(Topology::Square),
+            mask: None,
+            mines_per_cell: 1,
+            clue_rule: ClueRule::Exact,
+            clue_neighbourhood: None,
+            no_guess: false,
+            line_totals: false,
+            lives: 1,
+            time_limit: None,
+            fade_after: None,
+            fade_moves: None,
+            question_marks: false,
+        }
+    }
+}
+
 /*
  *  Définition du trait GameActions
  */
 trait GameActions {
-    fn reveal(&mut self, row: usize, col: usize);
-    fn mark_mine(&mut self, row: usize, col: usize);
-    fn initialize_game_on_first_play(&mut self, row: usize, col: usize);
+    fn reveal(&mut self, row: usize, col: usize) -> Vec<(usize, usize)>;
+    fn mark_mine(&mut self, row: usize, col: usize) -> bool;
+    fn initialize_game_on_first_play(&mut self, row: usize, col: usize) -> Vec<(usize, usize)>;
     fn count_mines_around(&self, row: usize, col: usize) -> u8;
     fn check_win(&mut self) -> bool;
 }
@@ -80,155 +260,199 @@ trait GameActions {
  */
 impl GameActions for Minesweeper {
     /*
-     *  Fonction pour révéler les cellules
+     *  Fonction pour révéler les cellules (renvoie les cases nouvellement révélées)
      */
-    fn reveal(&mut self, row: usize, col: usize) {
+    fn reveal(&mut self, row: usize, col: usize) -> Vec<(usize, usize)> {
+        let mut newly_revealed = Vec::new();
+
         // on vérifie si les coordonnées 'row' et 'col' sont hors de la grille de jeu.
-        if row >= self.configuration.board_size || col >= self.configuration.board_size {
-            return; // si c'est le cas, on sort de la fonction.
+        if !self.grid.contains(row, col) {
+            return newly_revealed; // si c'est le cas, on sort de la fonction.
         }
 
-        // on vérifie si la case a déjà été révélée.
-        if self.revealed.contains(&(row, col)){
-            return; // si elle a déjà été révélée, on sort de la fonction.
+        // on vérifie si la case a déjà été révélée (ou porte un drapeau, ou est un trou du plateau).
+        if self.grid.has(row, col, grid::REVEALED | grid::FLAGGED | grid::MASKED) {
+            return newly_revealed; // si elle a déjà été révélée, on sort de la fonction.
         }
 
         // on vérifie si la case contient une mine.
-        if self.mines.contains(&(row, col)) {
-            self.game_over = true;
-            return; // si la case contient une mine, le jeu est terminé.        
+        if self.grid.is_mine(row, col) {
+            self.lives_lost += 1;
+            self.grid.set(row, col, grid::EXPLODED);
+            if self.lives_lost < self.configuration.lives {
+                // s'il reste une vie au joueur, la mine est marquée de drapeaux et la partie continue.
+                let mines = self.grid.mines_at(row, col);
+                self.grid.set_flags(row, col, mines);
+                self.num_mark += mines as usize;
+                self.player_message = Some(format!("Boom ! You hit a mine and lost a life ({} left)...", self.configuration.lives - self.lives_lost));
+            } else {
+                self.game_over = true;
+            }
+            return newly_revealed; // si la case contient une mine, le jeu est terminé (ou le joueur perd une vie).
         }
 
-        // on compte le nombre de mines autour de la case.
-        let mines_around = self.count_mines_around(row, col);
-        self.revealed.insert((row, col));
-
-        // on met à jour le tableau du jeu en fonction du nombre de mines autour de la case.
-        self.board[row][col] = match mines_around {
-            0 => Cell::Empty,
-            n => Cell::Number(n),
-        };
+        // on propage la révélation avec une file d'attente explicite plutôt que par récursion,
+        // pour ne pas dépasser la taille de la pile sur les grands plateaux peu minés.
+        // une case est marquée révélée dès son ajout à la file, pour n'y être ajoutée qu'une fois.
+        let mut pending = VecDeque::from([(row, col)]);
+        self.grid.set(row, col, grid::REVEALED);
+        while let Some((r, c)) = pending.pop_front() {
+            self.num_revealed += 1;
+            self.grid.clear(r, c, grid::QUESTION);
+            newly_revealed.push((r, c));
 
-        // IA : si la case ne contient aucune mine à proximité (mines_around == 0).
-        if mines_around == 0 {
-            for r in row.saturating_sub(1)..=row + 1 {
-                for c in col.saturating_sub(1)..=col + 1 {
-                    // on révèle les cases adjacentes.
-                    self.reveal(r, c);
+            // si la case ne contient aucune mine à proximité, on révèle les cases adjacentes.
+            if self.count_mines_around(r, c) == 0 {
+                let first_added = pending.len();
+                pending.extend(self.grid.neighbours(r, c).filter(|&(nr, nc)| !self.grid.has(nr, nc, grid::REVEALED | grid::FLAGGED | grid::MINE)));
+                for &(nr, nc) in pending.range(first_added..) {
+                    self.grid.set(nr, nc, grid::REVEALED);
                 }
             }
         }
+
+        // en mode de mémoire, on retient quand chaque case a été révélée pour l'effacer plus tard.
+        if !self.revealed_at.is_empty() {
+            // le coup en cours n'est compté qu'une fois joué.
+            let stamp = Some((Instant::now(), self.moves + 1));
+            for &(r, c) in &newly_revealed {
+                self.revealed_at[r * self.grid.size() + c] = stamp;
+            }
+        }
+        newly_revealed
     }
     
     /*
      *  Fonction pour marquer une mine sur le plateau
      */
-    fn mark_mine(&mut self, row: usize, col: usize) {
+    fn mark_mine(&mut self, row: usize, col: usize) -> bool {
         // on vérifie si les coordonnées 'row' et 'col' sont en dehors des limites du tableau.
-        if row >= self.configuration.board_size || col >= self.configuration.board_size {
+        if !self.grid.contains(row, col) {
             self.player_message = Some("The row and/or column are not within the game boundaries...".to_string());
-            return; // si c'est le cas, on sort de la fonction en affichant un message au joueur.
+            return false; // si c'est le cas, on sort de la fonction en affichant un message au joueur.
         }
         
         // on vérifie si le joueur a déjà effectué sa première action de jeu.
         if !self.first_play{
             self.player_message = Some("You must reveal a square...".to_string());
-            return; // si ce n'est pas le cas, on sort de la fonction en affichant un message au joueur.
+            return false; // si ce n'est pas le cas, on sort de la fonction en affichant un message au joueur.
         }
 
-        // si la case est déjà révélée et marquée comme une mine, on la désactive en la changeant en une case non marquée.
-        if self.revealed.contains(&(row, col)) && self.board[row][col] == Cell::Mark {
-            match self.board[row][col] {
-                Cell::Mark => {
-                    self.board[row][col] = Cell::Mine; // on change la cellule d'état en mine.
-                    self.revealed.remove(&(row, col)); // on retire le drapeau du tableau.
-                    self.num_mark -= 1;                // on décrémente le nombre de mine.
-                },
-                _ => {
-                    // on ne fait rien si la cellule contient autre chose qu'un drapeau.
-                },
-            }
-        // sinon, si le nombre maximal de marquages de mines n'est pas atteint et que la case est non découverte ou une mine, on la marque comme un drapeau.
-        } else if self.num_mark < self.configuration.num_mines && self.board[row][col] == Cell::Undiscovered || self.board[row][col] == Cell::Mine {
-            match self.board[row][col] {
-                Cell::Undiscovered | Cell::Mine => {
-                    self.board[row][col] = Cell::Mark; // on change la cellule d'état en drapeau.
-                    self.revealed.insert((row, col));  // on ajoute le drapeau au tableau.
-                    self.num_mark += 1;                // on incrémente le nombre de mine.
-                },
-                _ => {
-                    // on ne fait rien si la cellule contient autre chose que rien ou une mine.
-                },
+        // les drapeaux d'une mine déjà touchée restent en place.
+        if self.grid.has(row, col, grid::EXPLODED) {
+            self.player_message = Some("This mine has already exploded...".to_string());
+            return false;
+        }
+
+        // un point d'interrogation termine le cycle : la case redevient vierge.
+        if self.grid.has(row, col, grid::QUESTION) {
+            self.grid.clear(row, col, grid::QUESTION);
+            return true;
+        }
+
+        // on ne peut pas marquer une case déjà révélée.
+        if self.grid.is_revealed(row, col) {
+            self.player_message = Some("This square has already been revealed...".to_string());
+            return false;
+        }
+
+        // une case vierge ne reçoit un drapeau que s'il en reste : sinon le coup est refusé
+        // (elle ne devient pas un point d'interrogation pour autant).
+        let flags = self.grid.flags_at(row, col);
+        if flags == 0 && self.num_mark >= self.configuration.num_mines {
+            self.player_message = Some("There are no flags left...".to_string());
+            return false;
+        }
+
+        // si la case peut contenir plus de mines que de drapeaux posés et que le nombre maximal de marquages de mines
+        // n'est pas atteint, on lui ajoute un drapeau.
+        if (flags as usize) < self.grid.mines_per_cell() && self.num_mark < self.configuration.num_mines {
+            self.grid.set_flags(row, col, flags + 1);
+            self.num_mark += 1; // on incrémente le nombre de mine.
+        // sinon, on retire tous ses drapeaux (pour un point d'interrogation, s'ils sont activés).
+        } else {
+            self.grid.set_flags(row, col, 0);
+            self.num_mark -= flags as usize; // on décrémente le nombre de mine.
+            if self.configuration.question_marks {
+                // le point d'interrogation ne compte pas dans la limite des drapeaux.
+                self.grid.set(row, col, grid::QUESTION);
             }
         }
+        true
     }
 
     /*
      *   Fonction pour générer les mines au premier tour
      */
-    fn initialize_game_on_first_play(&mut self, row: usize, col: usize) {
+    fn initialize_game_on_first_play(&mut self, row: usize, col: usize) -> Vec<(usize, usize)> {
         // on vérifie si ce n'est pas le premier coup du joueur.
-        if !self.first_play {
-
-            // on onitialise un générateur de nombres aléatoires.
-            let mut rng = rand::thread_rng();
+        if self.first_play {
+            return Vec::new();
+        }
+        // on onitialise un générateur de nombres aléatoires à partir de la graine de la partie.
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let empty_grid = self.grid.clone();
 
-            // on remplit la grille avec des mines jusqu'à atteindre le nombre de mines spécifié.
-            while self.mines.len() < self.configuration.num_mines {
+        // on liste les cases candidates : toutes sauf la première case jouée et ses voisines.
+        let safe_zone: Vec<(usize, usize)> = self.grid.neighbours(row, col).chain([(row, col)]).collect();
+        // une case pouvant contenir plusieurs mines y figure une fois par mine possible.
+        let size = self.grid.size();
+        let mines_per_cell = self.grid.mines_per_cell();
+        let mut candidates: Vec<(usize, usize)> = (0..self.grid.cell_count())
+            .map(|cell| (cell / size, cell % size))
+            .filter(|&(r, c)| !safe_zone.contains(&(r, c)) && !self.grid.is_masked(r, c))
+            .flat_map(|cell| std::iter::repeat_n(cell, mines_per_cell))
+            .collect();
 
-                // on génère des coordonnées aléatoires pour les mines.
-                let random_row = rng.gen_range(0..self.configuration.board_size);
-                let random_col = rng.gen_range(0..self.configuration.board_size);
+        // on ne peut pas poser plus de mines qu'il n'y a de cases candidates.
+        self.configuration.num_mines = self.configuration.num_mines.min(candidates.len());
 
-                // IA : on déclare une variable pour vérifier que les coordonnées aléatoires de la mine ne sont pas adjacents à la première case jouée par le joueur.
-                let is_adjacent = (random_row as isize - row as isize).abs() <= 1 && (random_col as isize - col as isize).abs() <= 1;
+        // sans deviner, on tire des plateaux jusqu'à en trouver un que le robot résout par déduction.
+        let attempts = if self.configuration.no_guess { MAX_NO_GUESS_ATTEMPTS } else { 1 };
+        for attempt in 1..=attempts {
+            self.grid = empty_grid.clone();
 
-                // on vérifie que la mine générée n'est pas adjacente à la première case jouée par le joueur et qu'elle n'a pas déjà été révélée.
-                if !is_adjacent && !self.revealed.contains(&(random_row, random_col)) {
-                    // on ajoute la mine au plateau du démineur.
-                    self.mines.insert((random_row, random_col));
-                    // on déclare cette case comme étant une mine.
-                    self.board[random_row][random_col] = Cell::Mine;
-                }
-            }    
+            // on tire les mines sans remise (mélange partiel de Fisher-Yates).
+            for placed in 0..self.configuration.num_mines {
+                let chosen = rng.gen_range(placed..candidates.len());
+                candidates.swap(placed, chosen);
+                let (mine_row, mine_col) = candidates[placed];
+                // on ajoute la mine au plateau, ce qui met à jour le compteur de ses voisines.
+                self.grid.place_mine(mine_row, mine_col);
+            }
+            // on calcule les chiffres affichés des variantes à partir du vrai nombre de mines.
+            self.grid.compute_clues(&mut rng);
 
-            // on révèle les cases adjacentes à la première case jouée.
-            for r in row.saturating_sub(1)..=row + 1 {
-                for c in col.saturating_sub(1)..=col + 1 {
-                    self.reveal(r as usize, c as usize);
+            if attempt == attempts {
+                if self.configuration.no_guess {
+                    self.player_message = Some("No board without guessing was found, this one may need a guess...".to_string());
                 }
+            } else if self.solves_without_guessing(row, col) {
+                break;
             }
-
-            // on met à jour la valeur de la variable du premier coup du joueur.
-            self.first_play = true;
         }
+
+        // on met à jour la valeur de la variable du premier coup du joueur.
+        self.first_play = true;
+
+        // on révèle la première case jouée (vide par construction, donc ses voisines aussi).
+        self.reveal(row, col)
     }
 
     /*
      *  Fonction pour déterminer les numéros autours des mines
      */
     fn count_mines_around(&self, row: usize, col: usize) -> u8 {
-        let mut count = 0;
-
-        // on parcourt les cases voisines (3x3) autour de la case spécifiée.
-        for r in row.saturating_sub(1)..=row + 1 {
-            for c in col.saturating_sub(1)..=col + 1 {
-                // on vérifie si la case est à l'intérieur des limites du plateau et contient une mine.
-                if r < self.configuration.board_size && c < self.configuration.board_size && self.mines.contains(&(r, c)) {
-                    count += 1; // on incrémente le compteur de mines.
-                }
-            }
-        }
-        count // on retourne le nombre de mines trouvées autour de la case.
+        // le nombre de mines voisines est tenu à jour par le plateau à chaque pose de mine.
+        self.grid.count(row, col)
     }
 
     /*
      *   Fonction pour vérifier la victoire
      */
     fn check_win(&mut self) -> bool {
-        // on calcule la condition de victoire en comparant le nombre de cases révélées et le nombre de cases marquées.
-        self.revealed.len() - self.num_mark == (self.configuration.board_size * self.configuration.board_size) - self.configuration.num_mines
-        // on retourne vrai si le joueur a gagné, sinon on retourne faux.
+        // le joueur a gagné lorsque toutes les cases sans mine sont révélées.
+        self.num_revealed == self.grid.playable_count() - self.grid.mined_cells()
     }
 }
 
@@ -237,28 +461,602 @@ impl GameActions for Minesweeper {
  */
 impl Minesweeper {
     pub fn new(game_config : BoardInfo) -> Self {
-        let board = vec![vec![Cell::Undiscovered; game_config.board_size]; game_config.board_size];
+        // on tire une graine au hasard pour une nouvelle partie.
+        Minesweeper::with_seed(game_config, rand::thread_rng().gen())
+    }
 
+    /*
+     *  Fonction qui crée une partie reproductible à partir d'une graine
+     */
+    pub fn with_seed(game_config : BoardInfo, seed : u64) -> Self {
+        let mut grid = Grid::new(game_config.board_size, game_config.layers, game_config.topology, game_config.neighbourhood.clone());
+        if let Some(mask) = &game_config.mask {
+            grid.apply_mask(mask);
+        }
+        grid.allow_stacks(game_config.mines_per_cell);
+        grid.set_clue_rule(game_config.clue_rule, game_config.clue_neighbourhood.clone());
+        let blind = game_config.fade_after.is_some() || game_config.fade_moves.is_some();
         Minesweeper {
-            board,
-            mines: HashSet::new(),
-            revealed: HashSet::new(),
+            revealed_at: if blind { vec![None; grid.cell_count()] } else { Vec::new() },
+            moves: 0,
+            actions: Vec::new(),
+            notation: Notation::Numeric,
+            assisted: false,
+            cursor: grid.center(),
+            grid,
+            num_revealed: 0,
             game_over: false,
             num_mark: 0,
             player_message: Some("".to_string()),
+            lives_lost: 0,
             first_play: false,
             configuration : game_config,
+            seed,
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie le contenu d'une case tel qu'il est affiché
+     */
+    pub(crate) fn cell(&self, row: usize, col: usize) -> Cell {
+        if self.grid.is_masked(row, col) {
+            Cell::Hole
+        } else if self.game_over && self.grid.has(row, col, grid::EXPLODED) {
+            // en fin de partie, on distingue les mines touchées et les drapeaux mal placés.
+            Cell::Exploded
+        } else if self.game_over && self.grid.flags_at(row, col) > self.grid.mines_at(row, col) {
+            Cell::WrongMark
+        } else if self.grid.is_flagged(row, col) {
+            Cell::Mark(self.grid.flags_at(row, col))
+        } else if self.grid.is_revealed(row, col) {
+            match self.count_mines_around(row, col) {
+                // un chiffre déformé est toujours affiché, même nul, pour ne rien révéler de plus.
+                _ if self.grid.distorted_clues() => Cell::Number(self.grid.clue(row, col)),
+                0 => Cell::Empty,
+                n => Cell::Number(n),
+            }
+        } else if self.grid.has(row, col, grid::QUESTION) && !(self.game_over && self.grid.is_mine(row, col)) {
+            // en fin de partie, une mine est montrée même sous un point d'interrogation.
+            Cell::Question
+        } else if self.grid.is_mine(row, col) {
+            Cell::Mine(self.grid.mines_at(row, col))
+        } else {
+            Cell::Undiscovered
+        }
+    }
+
+    /*
+     *  Fonction qui compte les drapeaux bien placés (sur une mine) et mal placés (en trop sur une case)
+     */
+    pub(crate) fn flag_report(&self) -> (usize, usize) {
+        let size = self.grid.size();
+        (0..self.grid.cell_count())
+            .map(|cell| (self.grid.flags_at(cell / size, cell % size) as usize, self.grid.mines_at(cell / size, cell % size) as usize))
+            .fold((0, 0), |(right, wrong), (flags, mines)| (right + flags.min(mines), wrong + flags.saturating_sub(mines)))
+    }
+
+    /*
+     *  Fonction qui révèle les voisines d'un chiffre entouré d'autant de drapeaux que de mines
+     *  (renvoie None si le chiffre ne peut pas être joué ainsi)
+     */
+    fn chord(&mut self, row: usize, col: usize) -> Option<Vec<(usize, usize)>> {
+        // un chiffre déformé ne dit pas combien de drapeaux il faut autour.
+        if self.grid.distorted_clues() {
+            self.player_message = Some("Chording needs exact numbers...".to_string());
+            return None;
+        }
+
+        // les points d'interrogation ne sont pas des drapeaux : ils sont révélés comme les autres cases.
+        let neighbours: Vec<(usize, usize)> = self.grid.neighbours(row, col).collect();
+        let flags: usize = neighbours.iter().map(|&(r, c)| self.grid.flags_at(r, c) as usize).sum();
+        if flags != self.count_mines_around(row, col) as usize {
+            self.player_message = Some("The number of flags around does not match this number...".to_string());
+            return None;
+        }
+
+        let mut newly_revealed = Vec::new();
+        for (r, c) in neighbours {
+            if self.game_over {
+                break;
+            }
+            newly_revealed.extend(self.reveal(r, c));
+        }
+        Some(newly_revealed)
+    }
+
+    /*
+     *  Fonction qui indique si le chiffre d'une case révélée est effacé de l'affichage (mode de mémoire)
+     */
+    pub(crate) fn is_faded(&self, row: usize, col: usize) -> bool {
+        // tout le plateau redevient visible une fois la partie terminée.
+        let finished = self.game_over || self.num_revealed == self.grid.playable_count() - self.grid.mined_cells();
+        match self.revealed_at.get(row * self.grid.size() + col) {
+            // seuls les chiffres s'effacent : les cases vides ne cachent rien à retenir.
+            Some(&Some((instant, move_index))) if !finished && matches!(self.cell(row, col), Cell::Number(_)) => {
+                self.configuration.fade_after.is_some_and(|after| instant.elapsed() >= after)
+                    || self.configuration.fade_moves.is_some_and(|moves| self.moves - move_index >= moves)
+            }
+            _ => false,
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie l'instant où le prochain chiffre encore visible s'effacera (mode de mémoire avec un temps)
+     */
+    pub(crate) fn next_fade(&self) -> Option<Instant> {
+        let after = self.configuration.fade_after?;
+        let size = self.grid.size();
+        self.revealed_at.iter().enumerate()
+            .filter_map(|(index, stamp)| stamp.map(|(instant, _)| (index / size, index % size, instant + after)))
+            .filter(|&(row, col, _)| matches!(self.cell(row, col), Cell::Number(_)) && !self.is_faded(row, col))
+            .map(|(_, _, instant)| instant)
+            .min()
+    }
+
+    /*
+     *  Fonction qui vérifie que le robot résout le plateau tiré sans deviner, à partir de la première case
+     */
+    fn solves_without_guessing(&mut self, row: usize, col: usize) -> bool {
+        // on joue une partie d'essai sur le plateau tiré, puis on remet la partie dans son état initial.
+        let drawn_grid = self.grid.clone();
+        let recorded = self.actions.len();
+        self.first_play = true;
+        self.reveal(row, col);
+        let report = autoplay::finish_game(self, None);
+
+        self.grid = drawn_grid;
+        self.first_play = false;
+        self.num_revealed = 0;
+        self.num_mark = 0;
+        self.lives_lost = 0;
+        self.moves = 0;
+        self.revealed_at.fill(None);
+        self.actions.truncate(recorded);
+        self.game_over = false;
+        self.cursor = (row, col);
+        self.player_message = Some("".to_string());
+        report.won && report.guesses == 0
+    }
+
+    /*
+     *  Fonction qui calcule le 3BV du plateau (nombre minimal de clics pour le résoudre)
+     */
+    pub(crate) fn three_bv(&self) -> usize {
+        let size = self.grid.size();
+        let mut counted = vec![false; self.grid.cell_count()];
+        let mut clicks = 0;
+
+        // chaque zone vide (avec sa bordure de chiffres) ne compte que pour un clic.
+        for cell in 0..self.grid.cell_count() {
+            let (row, col) = (cell / size, cell % size);
+            if counted[cell] || self.grid.has(row, col, grid::MINE | grid::MASKED) || self.count_mines_around(row, col) != 0 {
+                continue;
+            }
+            clicks += 1;
+            counted[cell] = true;
+            let mut pending = vec![(row, col)];
+            while let Some((r, c)) = pending.pop() {
+                for (nr, nc) in self.grid.neighbours(r, c) {
+                    if !counted[nr * size + nc] {
+                        counted[nr * size + nc] = true;
+                        if self.count_mines_around(nr, nc) == 0 {
+                            pending.push((nr, nc));
+                        }
+                    }
+                }
+            }
+        }
+
+        // chaque chiffre qui ne borde aucune zone vide demande son propre clic.
+        clicks += (0..self.grid.cell_count())
+            .filter(|&cell| !counted[cell] && !self.grid.has(cell / size, cell % size, grid::MINE | grid::MASKED))
+            .count();
+        clicks
+    }
+
+    /*
+     *  Fonction qui déplace le curseur (et donc la partie affichée) sur le plateau
+     */
+    pub(crate) fn move_cursor(&mut self, delta_row: isize, delta_col: isize) {
+        // sur un tore, le curseur fait le tour du plateau au lieu de s'arrêter au bord.
+        let step = |position: usize, delta: isize, length: usize| if self.grid.wraps() {
+            (position as isize + delta).rem_euclid(length as isize) as usize
+        } else {
+            (position as isize + delta).clamp(0, length as isize - 1) as usize
+        };
+        self.cursor = (step(self.cursor.0, delta_row, self.grid.rows()), step(self.cursor.1, delta_col, self.grid.size()));
+    }
+
+    /*
+     *  Fonction qui place le curseur sur une case donnée
+     */
+    pub(crate) fn jump_to(&mut self, row: usize, col: usize) {
+        if self.grid.contains(row, col) && !self.grid.is_masked(row, col) {
+            self.cursor = (row, col);
+        } else {
+            self.player_message = Some("The row and/or column are not within the game boundaries...".to_string());
+        }
+    }
+
+    /*
+     *  Fonction qui applique une action du joueur (ou du robot) sur le plateau
+     *  (renvoie les cases nouvellement révélées, pour que l'affichage puisse ne redessiner qu'elles)
+     */
+    pub(crate) fn apply_action(&mut self, action: Action) -> Vec<(usize, usize)> {
+        let (row, col) = match action {
+            Action::Reveal(row, col) | Action::Mark(row, col) | Action::Chord(row, col) => (row, col),
+        };
+
+        // on vérifie que ligne et colonne sont compris dans le tableau (et pas dans un trou de sa forme).
+        if !self.grid.contains(row, col) || self.grid.is_masked(row, col) {
+            // si la ligne et la colonne ne sont pas compris dans le tableau, on affiche un message d'erreur.
+            self.player_message = Some("The row and/or column are not within the game boundaries...".to_string());
+            return Vec::new();
+        }
+
+        // l'affichage suit la dernière case jouée.
+        self.cursor = (row, col);
+
+        // chaque action renvoie les cases révélées, ou rien si elle est refusée (avec un message au joueur).
+        let played = match (action, self.cell(row, col)) {
+            (Action::Reveal(..), _) if !self.first_play => {
+                // si c'est le premier coup, on initialise le jeu en disposant les mines de manière aléatoire.
+                Some(self.initialize_game_on_first_play(row, col))
+            }
+            (Action::Reveal(..), Cell::Undiscovered | Cell::Mine(_) | Cell::Question) => {
+                // si la case n'a pas été découverte, on la révèle.
+                Some(self.reveal(row, col))
+            }
+            (Action::Reveal(..) | Action::Chord(..), Cell::Number(_)) => {
+                // sur un chiffre déjà révélé, on révèle ses voisines (« chording »).
+                self.chord(row, col)
+            }
+            (Action::Chord(..), _) => {
+                self.player_message = Some("Only a revealed number can be chorded...".to_string());
+                None
+            }
+            (Action::Reveal(..), Cell::Mark(_)) => {
+                // un drapeau protège sa case : il faut le retirer avant de la révéler.
+                self.player_message = Some("This square is flagged : remove the flag before revealing it...".to_string());
+                None
+            }
+            (Action::Mark(..), Cell::Undiscovered | Cell::Mine(_) | Cell::Mark(_) | Cell::Question) => {
+                // si la case n'a pas été découverte, on ajoute (ou retire) un drapeau au tableau du démineur.
+                self.mark_mine(row, col).then(Vec::new)
+            }
+            _ => {
+                // si non, on affiche un message d'erreur.
+                self.player_message = Some("This square has already been revealed...".to_string());
+                None
+            }
+        };
+        let Some(newly_revealed) = played else {
+            return Vec::new();
+        };
+        // seuls les coups acceptés sont retenus et comptés, pour que l'annulation défasse un vrai coup.
+        self.moves += 1;
+        self.actions.push(action);
+        newly_revealed
+    }
+
+    /*
+     *  Fonction qui recommence le même plateau, en gardant trace de l'aide reçue lors des essais précédents
+     */
+    fn retry(game_config : BoardInfo, seed : u64, assisted : bool) -> Self {
+        let mut game = Minesweeper::with_seed(game_config, seed);
+        game.assisted = assisted;
+        game
+    }
+
+    /*
+     *  Fonction qui recrée une partie à partir de sa graine en rejouant ses coups
+     */
+    pub(crate) fn replay(game_config : BoardInfo, seed : u64, actions : &[Action]) -> Self {
+        let mut game = Minesweeper::with_seed(game_config, seed);
+        for &action in actions {
+            game.apply_action(action);
+        }
+        game.player_message = Some("".to_string());
+        game
+    }
+
+    /*
+     *  Fonction qui annule le dernier coup joué (la partie est rejouée sans lui)
+     */
+    pub(crate) fn undo(&mut self) {
+        // rejouer la partie rendrait les vies perdues et ferait réapparaître les chiffres effacés.
+        if self.configuration.lives > 1 || !self.revealed_at.is_empty() {
+            self.player_message = Some("Moves cannot be undone in games with lives or fading numbers...".to_string());
+            return;
+        }
+        let mut actions = std::mem::take(&mut self.actions);
+        if actions.pop().is_none() {
+            self.player_message = Some("There is no move to undo...".to_string());
+            return;
+        }
+        let notation = self.notation;
+        *self = Minesweeper::replay(self.configuration.clone(), self.seed, &actions);
+        self.notation = notation;
+        // reprendre un coup est une aide : la partie ne compte plus comme un record.
+        self.assisted = true;
+        self.player_message = Some("Last move undone.".to_string());
+    }
+
+    /*
+     *  Fonction qui convertit une position saisie par le joueur en case du plateau
+     *  (une position sans couche désigne la couche du curseur)
+     */
+    pub(crate) fn resolve(&self, position: Position) -> Result<(usize, usize), String> {
+        let size = self.grid.size();
+        let layer = match position.layer {
+            Some(_) if self.grid.layers() == 1 => return Err("This board is flat : positions are written 'row,col'...".to_string()),
+            Some(layer) if layer >= self.grid.layers() => {
+                return Err(format!("There is no layer {} : layers go from 0 to {}...", layer, self.grid.layers() - 1));
+            }
+            Some(layer) => layer,
+            None => self.cursor.0 / size,
+        };
+        // une ligne hors de sa couche est envoyée hors du plateau, pour que l'action soit refusée.
+        if position.row < size {
+            Ok((layer * size + position.row, position.col))
+        } else {
+            Ok((self.grid.rows(), position.col))
+        }
+    }
+
+    /*
+     *  Fonction qui écrit une case comme le joueur la saisit ('ligne,colonne', 'C12' ou 'couche,ligne,colonne' en 3D)
+     */
+    pub(crate) fn position_label(&self, row: usize, col: usize) -> String {
+        let size = self.grid.size();
+        if self.grid.layers() > 1 {
+            format!("{},{},{}", row / size, row % size, col)
+        } else {
+            self.notation.square(row, col)
+        }
+    }
+
+    /*
+     *  Fonction qui donne un indice au joueur : une case sûre, sinon une mine certaine, sinon la case la moins risquée
+     */
+    pub(crate) fn hint(&mut self) {
+        if !self.first_play {
+            self.player_message = Some("The first move is always safe : start anywhere !".to_string());
+            return;
+        }
+
+        // on ne signale que les mines qui n'ont pas encore de drapeau.
+        let analysis = solver::analyse(self);
+        let mine = analysis.mines.iter().copied().find(|&(row, col)| !self.grid.is_flagged(row, col));
+        let (cell, message) = if let Some(&(row, col)) = analysis.safe.first() {
+            ((row, col), format!("Hint : {} is safe.", self.position_label(row, col)))
+        } else if let Some((row, col)) = mine {
+            ((row, col), format!("Hint : {} is certainly a mine.", self.position_label(row, col)))
+        } else if let Some(((row, col), probability)) = analysis.best_guess {
+            let label = self.position_label(row, col);
+            ((row, col), format!("Hint : nothing is certain, {} is the least risky guess ({:.0}% chance of a mine).", label, probability * 100.0))
+        } else {
+            self.player_message = Some("There is nothing left to play...".to_string());
+            return;
+        };
+        // l'affichage se place sur la case conseillée, et la partie est désormais aidée.
+        self.cursor = cell;
+        self.assisted = true;
+        self.player_message = Some(message);
+    }
+}
+
+/*
+ *  Implémentation de la conversion d'un mode de jeu en configuration de plateau
+ */
+impl GameMode {
+    fn board_info(&self) -> BoardInfo {
+        BoardInfo {
+            name: self.name.clone(),
+            board_size: self.board_size as usize,
+            num_mines: self.num_mines as usize,
+            layers: self.depth.max(1) as usize,
+            topology: self.topology,
+            // le voisinage et le masque ont été vérifiés au chargement de la configuration.
+            neighbourhood: self.neighbourhood().expect("the configuration is validated when loaded"),
+            mask: self.mask.as_ref().map(|mask| mask.load(self.board_size as usize).expect("the configuration is validated when loaded")),
+            mines_per_cell: self.mines_per_cell.max(1),
+            clue_rule: self.clues,
+            clue_neighbourhood: self.clue_neighbourhood().expect("the configuration is validated when loaded"),
+            no_guess: self.no_guess,
+            line_totals: self.line_totals,
+            lives: self.lives.max(1) as usize,
+            time_limit: Some(Duration::from_secs(self.time_limit as u64)).filter(|limit| !limit.is_zero()),
+            fade_after: Some(Duration::from_secs(self.fade_seconds as u64)).filter(|after| !after.is_zero()),
+            fade_moves: Some(self.fade_moves as usize).filter(|&moves| moves > 0),
+            question_marks: self.question_marks,
+        }
+    }
+
+    /*
+     *  Fonction qui construit le voisinage compté par les chiffres, s'il diffère de celui des cases
+     */
+    fn clue_neighbourhood(&self) -> Result<Option<Neighbourhood>, String> {
+        match &self.clue_neighbourhood {
+            Some(_) if self.depth > 1 => Err("3D boards only support the square neighbourhood for their numbers".to_string()),
+            Some(offsets) => Neighbourhood::custom(offsets, self.topology).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /*
+     *  Fonction qui construit le voisinage des cases du mode de jeu
+     */
+    fn neighbourhood(&self) -> Result<Neighbourhood, String> {
+        match &self.neighbourhood {
+            _ if self.depth > 1 && (self.topology != Topology::Square || self.neighbourhood.is_some()) => {
+                Err("3D boards only support the square topology and neighbourhood".to_string())
+            }
+            _ if self.depth > 1 => Ok(Neighbourhood::cube()),
+            Some(offsets) => Neighbourhood::custom(offsets, self.topology),
+            None => Ok(Neighbourhood::of(self.topology)),
+        }
+    }
+
+    /*
+     *  Fonction qui décrit le mode de jeu dans le menu
+     */
+    fn description(&self) -> String {
+        let mut description = self.shape_description();
+        if self.lives > 1 {
+            description = format!("{} {} lives.", description, self.lives);
+        }
+        if self.time_limit > 0 {
+            description = format!("{} {}:{:02} on the clock.", description, self.time_limit / 60, self.time_limit % 60);
+        }
+        if self.time_attack > 0 {
+            description = format!("{} Clear as many boards as you can in {} minute(s).", description, self.time_attack);
+        }
+        match (self.fade_seconds, self.fade_moves) {
+            (0, 0) => {}
+            (seconds, 0) => description = format!("{} Numbers fade after {} seconds.", description, seconds),
+            (0, moves) => description = format!("{} Numbers fade after {} moves.", description, moves),
+            (seconds, moves) => description = format!("{} Numbers fade after {} seconds or {} moves.", description, seconds, moves),
+        }
+        if self.no_guess {
+            description = format!("{} No guess.", description);
+        }
+        description
+    }
+
+    /*
+     *  Fonction qui décrit le plateau du mode de jeu
+     */
+    fn shape_description(&self) -> String {
+        if self.endless {
+            format!("{} infinite field with {} mines per {}x{} area.", self.name, self.num_mines, self.board_size, self.board_size)
+        } else if self.line_totals {
+            format!("{} {}x{} grid with {} mines and the mine count of each row and column.", self.name, self.board_size, self.board_size, self.num_mines)
+        } else if self.clues == ClueRule::Liar {
+            format!("{} {}x{} grid with {} mines and numbers off by one.", self.name, self.board_size, self.board_size, self.num_mines)
+        } else if self.clues == ClueRule::Parity {
+            format!("{} {}x{} grid with {} mines and numbers showing only parity.", self.name, self.board_size, self.board_size, self.num_mines)
+        } else if let Some(offsets) = &self.clue_neighbourhood {
+            format!("{} {}x{} grid with {} mines and numbers counting {} squares.", self.name, self.board_size, self.board_size, self.num_mines, offsets.len())
+        } else if self.depth > 1 {
+            format!("{} {}x{}x{} 3D grid with {} mines.", self.name, self.board_size, self.board_size, self.depth, self.num_mines)
+        } else if self.mines_per_cell > 1 {
+            format!("{} {}x{} grid with {} mines, up to {} per square.", self.name, self.board_size, self.board_size, self.num_mines, self.mines_per_cell)
+        } else if self.mask.is_some() {
+            format!("{} shaped grid (up to {}x{}) with {} mines.", self.name, self.board_size, self.board_size, self.num_mines)
+        } else if self.topology == Topology::Hex {
+            format!("{} {}x{} hexagonal grid with {} mines.", self.name, self.board_size, self.board_size, self.num_mines)
+        } else if self.topology == Topology::Torus {
+            format!("{} {}x{} wrap-around grid with {} mines.", self.name, self.board_size, self.board_size, self.num_mines)
+        } else if let Some(offsets) = &self.neighbourhood {
+            format!("{} {}x{} grid with {} mines and {} neighbours per square.", self.name, self.board_size, self.board_size, self.num_mines, offsets.len())
+        } else {
+            format!("{} {}x{} grid with {} mines.", self.name, self.board_size, self.board_size, self.num_mines)
         }
     }
 }
 
+/*
+ *  Implémentation de la vérification de la configuration
+ */
+impl Config {
+    /*
+     *  Fonction qui vérifie que chaque mode de jeu est jouable
+     */
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        for mode in &self.game_modes {
+            if mode.endless && (mode.neighbourhood.is_some() || mode.depth > 1) {
+                return Err(format!("game mode '{}' : the endless mode only supports the flat square neighbourhood", mode.name));
+            }
+            let neighbourhood = mode.neighbourhood().map_err(|err| format!("game mode '{}' : {}", mode.name, err))?;
+            // les variantes de chiffres et les plateaux sans hasard ne s'appliquent qu'aux plateaux finis.
+            if mode.endless && (mode.clues != ClueRule::Exact || mode.clue_neighbourhood.is_some() || mode.no_guess || mode.line_totals) {
+                return Err(format!("game mode '{}' : the endless mode only supports exact numbers", mode.name));
+            }
+            if mode.endless && (mode.lives > 1 || mode.time_limit > 0 || mode.time_attack > 0 || mode.fade_seconds > 0 || mode.fade_moves > 0 || mode.question_marks) {
+                return Err(format!("game mode '{}' : the endless mode does not support lives, timers, fading numbers or question marks", mode.name));
+            }
+            if mode.time_limit > 0 && mode.time_attack > 0 {
+                return Err(format!("game mode '{}' : a mode is either a countdown or a time attack", mode.name));
+            }
+            if mode.line_totals && mode.depth > 1 {
+                return Err(format!("game mode '{}' : row and column totals are only given on flat boards", mode.name));
+            }
+            let clue_neighbourhood = mode.clue_neighbourhood().map_err(|err| format!("game mode '{}' : {}", mode.name, err))?;
+            // les voisinages qui font le tour du plateau (celui des cases comme celui des chiffres) doivent y tenir.
+            for checked in [Some(&neighbourhood), clue_neighbourhood.as_ref()].into_iter().flatten() {
+                checked.fits(mode.board_size as usize).map_err(|err| format!("game mode '{}' : {}", mode.name, err))?;
+            }
+            // un chiffre doit tenir sur deux caractères à l'affichage.
+            let counted = clue_neighbourhood.as_ref().unwrap_or(&neighbourhood).max_neighbours();
+            if mode.mines_per_cell > grid::MAX_STACK {
+                return Err(format!("game mode '{}' : a square holds at most {} mines", mode.name, grid::MAX_STACK));
+            }
+            if (mode.mines_per_cell > 1 && mode.endless) || mode.mines_per_cell.max(1) as usize * counted > MAX_CLUE {
+                return Err(format!("game mode '{}' : too many mines per square for this board (numbers must stay below {})", mode.name, MAX_CLUE + 1));
+            }
+            if let Some(mask) = &mode.mask {
+                if mode.endless {
+                    return Err(format!("game mode '{}' : the endless mode cannot be shaped by a mask", mode.name));
+                }
+                // il faut au moins une case jouable de plus que de mines (et la zone sûre du premier coup).
+                let playable = mask.load(mode.board_size as usize).map_err(|err| format!("game mode '{}' : {}", mode.name, err))?;
+                if playable.iter().filter(|&&square| square).count() <= mode.num_mines as usize {
+                    return Err(format!("game mode '{}' : the mask leaves fewer squares than mines", mode.name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /*
  *  Fonction qui gère la saisie du joueur sur le plateau 
  */
-pub fn play(game_config : BoardInfo) {
-    // on déclare une nouvelle instance du jeu Minesweeper.
-    let mut game_instance = Minesweeper::new(game_config);
+fn play(game_config : BoardInfo, notation : Notation, retry : Option<(u64, bool)>) -> NextStep {
+    // un mode avec compte à rebours est perdu lorsque le temps imparti est écoulé.
+    let deadline = game_config.time_limit.map(|limit| Instant::now() + limit);
+    // on rejoue la même graine si le joueur recommence le même plateau.
+    let mut game_instance = match retry {
+        Some((seed, assisted)) => Minesweeper::retry(game_config, seed, assisted),
+        None => resume_or_new(game_config),
+    };
+    game_instance.notation = notation;
+    play_until(game_instance, deadline, "").2
+}
+
+/*
+ *  Fonction qui propose de reprendre la partie sauvegardée de ce mode, s'il y en a une
+ */
+fn resume_or_new(game_config : BoardInfo) -> Minesweeper {
+    if SavedGame::mode_of(save::SAVE_PATH).as_deref() == Some(game_config.name.as_str()) {
+        println!("A saved game of this mode was found. Enter 'load' to continue it, or anything else for a new game :");
+        let mut input = String::new();
+        read::read_user_input(&mut input);
+        if input.trim() == "load" {
+            // le plateau est régénéré à partir de la graine, puis les coups sauvegardés sont rejoués.
+            return match SavedGame::take(save::SAVE_PATH) {
+                Ok(saved) => {
+                    let mut game = Minesweeper::replay(game_config, saved.seed, &saved.actions);
+                    game.assisted = saved.assisted;
+                    game
+                }
+                Err(err) => {
+                    let mut game = Minesweeper::new(game_config);
+                    game.player_message = Some(format!("The saved game could not be loaded : {}", err));
+                    game
+                }
+            };
+        }
+    }
+    Minesweeper::new(game_config)
+}
 
+/*
+ *  Fonction qui joue une partie, perdue si elle n'est pas finie avant l'échéance éventuelle
+ *  (renvoie l'issue de la partie, le 3BV du plateau et la suite choisie par le joueur s'il l'a quittée)
+ */
+fn play_until(mut game_instance : Minesweeper, deadline : Option<Instant>, banner : &str) -> (Outcome, usize, NextStep) {
     // on initialise une nouvelle chaîne modifiable.
     let mut input = String::new();
 
@@ -266,7 +1064,7 @@ pub fn play(game_config : BoardInfo) {
     let mut start_time: Option<Instant> = None;
 
     // on vérifie que le timer n'est pas encore initialisée.
-    if let None = start_time {
+    if start_time.is_none() {
         // on initialise `start_time` en lui attribuant la valeur actuelle de l'instant.
         start_time = Some(Instant::now());
     }
@@ -278,101 +1076,77 @@ pub fn play(game_config : BoardInfo) {
         display::print_board(&mut game_instance);
 
         // on explique au joueur comment jouer.
-        println!("Enter row and column (e.g., '2,0' , '12,19') or mark a mine (e.g., '3,3!' , '5,15!') :");
+        println!("Other commands : 'chord row,col', 'goto row,col', 'w' 'a' 's' 'd' to scroll, 'hint', 'undo', 'save', 'auto', 'restart', 'retry', 'menu', 'quit', 'help'.");
+        match game_instance.notation {
+            Notation::Numeric => println!("Enter row and column (e.g., '2,0' , '12,19') or mark a mine (e.g., '3,3!' , '5,15!'), several at once or ranges (e.g., '2-4,5!') :"),
+            Notation::Spreadsheet => println!("Enter column and row (e.g., 'A2' , 'T12') or mark a mine (e.g., 'D3!' , 'P5!'), several at once or ranges (e.g., 'B2-D4!') :"),
+        }
+
+        // on réserve au compte à rebours la ligne juste au-dessus de la saisie : elle est courte et ne passe
+        // jamais à la ligne, contrairement aux explications au-dessus, selon la largeur du terminal.
+        if deadline.is_some() {
+            println!();
+        }
 
         // on efface la valeur d'input pour ne pas avoir de bug.
         input.clear();
         
-        // on récupère la saisie du joueur.
-        read::read_user_input(&mut input);
-
-        // Traitement de la saisie utilisateur.
-        let chars: Vec<char> = input.chars().collect();                             // On extrait chaque caractère de la saisie du joueur dans un vecteur de caractères.
-        let before_last_chars = chars[chars.len() - 2];                       // On extrait le caractère avant le dernier de la saisie.
-        let contains_commas = input.contains(',');                            // On vérifie si la saisie contient une virgule.
-        let num_commas = input.chars().filter(|&c| c == ',').count();  // IA : on compte le nombre de virgules dans la saisie.
-        let contains_exclamation = input.contains('!');                       // on vérifie si la saisie contient un point d'exclamation.
-        let input = input.replace("!", "");                        // on supprime les points d'exclamation de la saisie.
-
-        // on vérifie que le joueur a saisi une seule virgule.
-        if contains_commas && num_commas == 1 {
-        
-            // IA : on divise la saisie en parties distinctes en utilisant la virgule comme séparateur et on les stocke dans un vecteur.
-            let parts: Vec<&str> = input.trim().split(',').collect();
-
-            // On regarde si le joueur veut découvrir une cellule ou poser un drapeau.
-            if !contains_exclamation && parts.len() == 2 { // le joueur veut découvrir une cellule.
-
-                // IA : on convertit les deux parties de la saisie (ligne et colonne) en valeurs numériques de type `usize`.
-                if let (Some(row),
-                        Some(col)) =
-                            (parts[0].parse::<usize>().ok(),
-                             parts[1].parse::<usize>().ok()) { 
-                    // on vérifie que ligne et colonne sont compris dans le tableau BOARD_SIZE.
-                    if row < game_instance.configuration.board_size && col < game_instance.configuration.board_size {
-
-                        // si ligne et colonne sont compris dans le tableau, on vérifie si c'est le premier coup du joueur.
-                        if !game_instance.first_play { 
-                            // si c'est le premier coup, on initialise le jeu en disposant les mines de manière aléatoire.
-                            game_instance.initialize_game_on_first_play(row, col);
-                        } else {
-                            // si ce n'est pas le premier coup, on vérifie que la case n'a pas été découverte.
-                            if game_instance.board[row][col] == Cell::Undiscovered || 
-                               game_instance.board[row][col] == Cell::Mine { 
-                                // si oui, on révèle la case du jeu située à la position spécifiée par les valeurs de 'row' et 'col'.
-                                game_instance.reveal(row as usize, col as usize);
-                            } else {
-                                // si non, on affiche un message d'erreur.
-                                game_instance.player_message = Some("This square has already been revealed...".to_string());
-                            }
-                        }
-                    } else {
-                        // si la ligne et la colonne ne sont pas compris dans le tableau, on affiche un message d'erreur.
-                        game_instance.player_message = Some("The row and/or column are not within the game boundaries...".to_string());
+        // on récupère la saisie du joueur, avant la fin du temps imparti s'il y en a un,
+        // en l'interrompant quand un chiffre doit s'effacer (mode de mémoire) pour redessiner le plateau.
+        let fade = game_instance.next_fade();
+        match deadline.into_iter().chain(fade).min() {
+            Some(until) => {
+                let countdown = |_| if let Some(deadline) = deadline {
+                    display::print_countdown(deadline.saturating_duration_since(Instant::now()), banner);
+                };
+                if !read::read_user_input_until(&mut input, until, countdown) {
+                    // un chiffre s'efface avant la fin du temps : on redessine le plateau, et le joueur continue sa saisie.
+                    if deadline.is_none_or(|deadline| Instant::now() < deadline) {
+                        continue;
                     }
-                } else {
-                    // si le joueur a saisi une virgule mais pas de chiffre, on affiche un message d'erreur.
-                    game_instance.player_message = Some("Please enter a digit or a number as per the instructions below...".to_string());
+                    // le temps est écoulé : la partie est perdue.
+                    game_instance.game_over = true;
+                    display::print_board(&mut game_instance);
+                    println!("Time is up ! \u{23F0}\n");
+                    let elapsed_time = start_time.map(|start| start.elapsed()).unwrap_or_default();
+                    record_result(&game_instance, Outcome::TimedOut, elapsed_time);
+                    return (Outcome::TimedOut, game_instance.three_bv(), NextStep::Ask);
                 }
             }
+            None => {
+                read::read_user_input(&mut input);
+            }
+        }
 
-            if contains_exclamation && before_last_chars == '!' && parts.len() == 2 { // le joueur veut poser un drapeau.
-
-                // IA : on convertit les deux parties de la saisie (ligne et colonne) en valeurs numériques de type `usize`.
-                if let (Some(row),
-                        Some(col)) =
-                            (parts[0].parse::<usize>().ok(),
-                             parts[1].parse::<usize>().ok()) { 
-
-                    // on vérifie que ligne et colonne sont compris dans le tableau BOARD_SIZE.
-                    if row < game_instance.configuration.board_size && col < game_instance.configuration.board_size { 
-                        // on vérifie que la case n'a pas été découverte.
-                        if game_instance.board[row][col] == Cell::Undiscovered || 
-                           game_instance.board[row][col] == Cell::Mine || 
-                           game_instance.board[row][col] == Cell::Mark {
-                            // si oui, on ajoute un drapeau au tableau du démineur.
-                            game_instance.mark_mine(row as usize, col as usize);
-                        } else {
-                            // si non, on affiche un message d'erreur.
-                            game_instance.player_message = Some("This square has already been revealed...".to_string());
+        // à la fin de l'entrée (saisie vide, sans même un retour à la ligne), le joueur ne peut plus jouer : il abandonne.
+        let commands = if input.is_empty() { Ok(vec![Command::Quit]) } else { command::parse(&input) };
+        match commands {
+            Err(err) => game_instance.player_message = Some(err.to_string()),
+            Ok(commands) => {
+                // les commandes d'une même ligne sont jouées dans l'ordre, jusqu'à la première qui termine la partie.
+                for command in commands {
+                    if let Some(next) = run_command(&mut game_instance, command, deadline.is_some()) {
+                        // une partie commencée puis abandonnée compte dans les résultats.
+                        if game_instance.first_play {
+                            record_result(&game_instance, Outcome::Abandoned, start_time.map(|start| start.elapsed()).unwrap_or_default());
+                            println!("\nGame abandoned.\n");
                         }
-                    } else {
-                        // si la ligne et la colonne ne sont pas compris dans le tableau, on affiche un message d'erreur.
-                        game_instance.player_message = Some("The row and/or column are not within the game boundaries...".to_string());
+                        return (Outcome::Abandoned, game_instance.three_bv(), next);
+                    }
+                    if game_instance.game_over || game_instance.check_win() {
+                        break;
                     }
-                } else {
-                    // si le joueur a saisi une virgule mais pas de chiffre, on affiche un message d'erreur.
-                    game_instance.player_message = Some("Please enter a digit or a number as per the instructions below...".to_string());
                 }
             }
-        } else {
-            // si le joeur n'a pas utilisé de virgule, on affiche un message d'erreur.
-            game_instance.player_message = Some("You must adhere to the notation using a comma...".to_string());
         }
 
         if game_instance.game_over { // on vérifie la valeur de game_over (booléen) dans la structure de jeu.
             display::print_board(&mut game_instance); // on affiche le tableau de jeu.
-            println!("Game Over ! You hit a mine.\n"); // on informe le joueur qu'il a perdu.
+            println!("Game Over ! You hit a mine."); // on informe le joueur qu'il a perdu.
+            // on fait le bilan des drapeaux posés.
+            let (right, wrong) = game_instance.flag_report();
+            println!("   {} flag(s) on mines, {} wrong flag(s) \u{274C}.\n", right, wrong);
+            record_result(&game_instance, Outcome::Lost, start_time.map(|start| start.elapsed()).unwrap_or_default());
         }
 
         if game_instance.check_win() { // on vérifie si le joueur a découvert toutes les cases.
@@ -386,10 +1160,173 @@ pub fn play(game_config : BoardInfo) {
                 }
             };
             println!("Congratulations ! You won ! \u{1F389}"); // on informe le joueur qu'il a gagné.
+            // avec plusieurs vies, on distingue les victoires sans aucune mine touchée.
+            if game_instance.assisted {
+                println!("   You had some help : this win does not count as a record.");
+            } else if game_instance.configuration.lives > 1 {
+                match game_instance.lives_lost {
+                    0 => println!("   Perfect clear : you did not lose a single life ! \u{2728}"),
+                    lost => println!("   You lost {} of your {} lives on the way.", lost, game_instance.configuration.lives),
+                }
+            }
             // on affiche le temps que le joueur a passé dans un format lisible.
             println!("   Your time is {:02}:{:02}:{:03}\n", elapsed_time.as_secs() / 60, elapsed_time.as_secs() % 60, elapsed_time.subsec_millis());
+            record_result(&game_instance, Outcome::Won, elapsed_time);
+        }
+    }
+
+    let outcome = if game_instance.check_win() { Outcome::Won } else { Outcome::Lost };
+    (outcome, game_instance.three_bv(), NextStep::Ask)
+}
+
+/*
+ *  Fonction qui enchaîne les plateaux pendant `minutes` minutes, en cumulant le 3BV des plateaux déminés
+ */
+fn time_attack(game_config : BoardInfo, minutes : u32, notation : Notation) -> NextStep {
+    let deadline = Instant::now() + Duration::from_secs(minutes as u64 * 60);
+    let (mut boards, mut score) = (0, 0);
+    let mut input = String::new();
+    let mut retry = None;
+    let mut next = NextStep::Ask;
+
+    // on joue tant qu'il reste du temps, chaque plateau déminé rapportant son 3BV.
+    loop {
+        let banner = format!("Score {} ({} board(s)) - ", score, boards);
+        let mut game_instance = match retry.take() {
+            Some((seed, assisted)) => Minesweeper::retry(game_config.clone(), seed, assisted),
+            None => Minesweeper::new(game_config.clone()),
+        };
+        game_instance.notation = notation;
+        let (outcome, three_bv, step) = play_until(game_instance, Some(deadline), &banner);
+        if outcome == Outcome::Won {
+            boards += 1;
+            score += three_bv;
+        }
+        // le chronomètre continue de tourner quand le joueur recommence un plateau.
+        match step {
+            NextStep::Restart => continue,
+            NextStep::Retry(seed, assisted) => {
+                retry = Some((seed, assisted));
+                continue;
+            }
+            NextStep::Quit | NextStep::Menu => {
+                next = step;
+                break;
+            }
+            NextStep::Ask => {}
+        }
+        if outcome == Outcome::TimedOut {
+            break;
+        }
+
+        // le chronomètre continue de tourner entre deux plateaux.
+        println!("   Score {} ({} board(s) cleared). Press Enter for the next board...", score, boards);
+        input.clear();
+        if !read::read_user_input_until(&mut input, deadline, |_| {}) {
+            break;
+        }
+    }
+    println!("Time attack over ! You cleared {} board(s) for a score of {}.\n", boards, score);
+    next
+}
+
+/*
+ *  Fonction qui enregistre le résultat d'une partie terminée dans le fichier des résultats
+ */
+fn record_result(game: &Minesweeper, outcome: Outcome, elapsed: Duration) {
+    let blind = !game.revealed_at.is_empty();
+    let result = GameResult::new(&game.configuration.name, game.seed, outcome, elapsed, game.lives_lost, blind, game.assisted);
+    if let Err(err) = result.record(results::RESULTS_PATH) {
+        eprintln!("The result of the game could not be recorded : {}", err);
+    }
+}
+
+/*
+ *  Fonction qui demande au joueur de confirmer l'abandon d'une partie commencée
+ *  (la fin de l'entrée vaut confirmation : le joueur ne peut plus jouer)
+ */
+fn confirm_leaving(game: &mut Minesweeper) -> bool {
+    !game.first_play || confirm("This game is in progress : enter 'y' to abandon it, or anything else to keep playing :", &mut game.player_message)
+}
+
+/*
+ *  Fonction qui pose une question au joueur et renvoie sa confirmation ('y'), ou le prévient qu'il continue de jouer
+ */
+fn confirm(question: &str, player_message: &mut Option<String>) -> bool {
+    println!("{}", question);
+    let mut input = String::new();
+    read::read_user_input(&mut input);
+    let confirmed = input.is_empty() || matches!(input.trim().to_lowercase().as_str(), "y" | "yes");
+    if !confirmed {
+        *player_message = Some("Let's keep playing !".to_string());
+    }
+    confirmed
+}
+
+/*
+ *  Fonction qui exécute une commande du joueur (renvoie la suite choisie si le joueur quitte la partie)
+ */
+fn run_command(game: &mut Minesweeper, command: Command, timed: bool) -> Option<NextStep> {
+    match command {
+        Command::Reveal(position) => play_at(game, position, Action::Reveal),
+        Command::Flag(position) => play_at(game, position, Action::Mark),
+        Command::Chord(position) => play_at(game, position, Action::Chord),
+        // on déplace la partie affichée du plateau.
+        Command::Goto(position) => match game.resolve(position) {
+            Ok((row, col)) => game.jump_to(row, col),
+            Err(err) => game.player_message = Some(err),
+        },
+        Command::Scroll(delta_row, delta_col) => game.move_cursor(delta_row * SCROLL_STEP, delta_col * SCROLL_STEP),
+        // en 3D, on passe à la couche précédente ou suivante.
+        Command::Layer(delta) => {
+            let size = game.grid.size() as isize;
+            game.move_cursor(delta * size, 0);
+        }
+        Command::Hint => game.hint(),
+        Command::Undo => game.undo(),
+        Command::Save => save_game(game, timed),
+        Command::Help => game.player_message = Some(command::HELP.to_string()),
+        Command::Auto => {
+            // le joueur laisse le robot terminer la partie, qui n'est plus la sienne.
+            game.assisted = true;
+            let report = autoplay::finish_game(game, Some(Duration::from_millis(args::DEFAULT_AUTOPLAY_DELAY_MS)));
+            // on informe le joueur du nombre de coups hasardeux du robot.
+            game.player_message = Some(autoplay::report_message(&report));
         }
+        // le joueur quitte la partie : pour le jeu, pour le menu, ou pour recommencer.
+        Command::Quit => return Some(NextStep::Quit).filter(|_| confirm_leaving(game)),
+        Command::Menu => return Some(NextStep::Menu).filter(|_| confirm_leaving(game)),
+        Command::Restart => return Some(NextStep::Restart).filter(|_| confirm_leaving(game)),
+        Command::Retry => return Some(NextStep::Retry(game.seed, game.assisted)).filter(|_| confirm_leaving(game)),
     }
+    None
+}
+
+/*
+ *  Fonction qui joue une action sur la case saisie par le joueur
+ */
+fn play_at(game: &mut Minesweeper, position: Position, action: fn(usize, usize) -> Action) {
+    match game.resolve(position) {
+        Ok((row, col)) => {
+            game.apply_action(action(row, col));
+        }
+        Err(err) => game.player_message = Some(err),
+    }
+}
+
+/*
+ *  Fonction qui sauvegarde la partie en cours pour la reprendre depuis le menu
+ */
+fn save_game(game: &mut Minesweeper, timed: bool) {
+    // une partie chronométrée ou de mémoire ne peut pas être mise en pause.
+    if timed || !game.revealed_at.is_empty() {
+        game.player_message = Some("Timed and memory games cannot be saved...".to_string());
+        return;
+    }
+    game.player_message = Some(match SavedGame::of(game).save(save::SAVE_PATH) {
+        Ok(()) => format!("Game saved in '{}' : choose this mode again from the menu to resume it.", save::SAVE_PATH),
+        Err(err) => format!("The game could not be saved : {}", err),
+    });
 }
 
 fn play_again () -> u32 {
@@ -402,8 +1339,10 @@ fn play_again () -> u32 {
 
     loop {
         println!("Enter your choice (e.g., '1', '3') :");
-        // on récupère la saisie du joueur.
-        read::read_user_input(&mut input);
+        // on récupère la saisie du joueur (à la fin de l'entrée, on quitte le jeu).
+        if !read::read_user_input(&mut input) {
+            return 3;
+        }
 
         // on analyse l'entrée de l'utilisateur.        
         match input.trim().parse() {
@@ -425,7 +1364,7 @@ fn play_again () -> u32 {
 /*
  *  Fonction principale du jeu du Démineur 
  */
-pub fn main_game() {
+pub fn main_game() -> ExitCode {
     // on déclare une variable pour suivre l'état du jeu.
     let mut is_game_on = true;
 
@@ -442,21 +1381,76 @@ pub fn main_game() {
             // on affiche une erreur en cas de problème de lecture.
             eprintln!("Incorrect configuration file reading error : {}", err);
             // on quitte la fonction en cas d'erreur.
-            return;
+            return ExitCode::FAILURE;
         }
     };
 
-    // IA : on trouve l'index maximal parmi les modes de jeu définis dans la configuration.
-    let max_index = match config.game_modes.iter().map(|mode| mode.index).max() {
-        Some(max_index) => max_index as u32,
-        None => 0,
+    // on analyse les arguments de la ligne de commande.
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let notation = match args::parse_args(&arguments) {
+        Ok(args::Command::Menu { notation }) => notation,
+        Ok(args::Command::Autoplay { index, delay }) => {
+            // on fait jouer le robot sur le mode de jeu demandé.
+            match config.game_modes.iter().find(|&mode| mode.index == index) {
+                Some(mode) if !mode.endless => {
+                    autoplay::play(mode.board_info(), delay);
+                    return ExitCode::SUCCESS;
+                }
+                Some(_) => eprintln!("The bot cannot play the endless mode."),
+                None => eprintln!("Incorrect game mode."),
+            }
+            return ExitCode::FAILURE;
+        }
+        Ok(args::Command::Script(options)) => {
+            // on joue les coups du script sur le mode de jeu demandé, sans interaction.
+            match config.game_modes.iter().find(|&mode| mode.index == options.index) {
+                Some(mode) if !mode.endless => return script::run(mode.board_info(), &options),
+                Some(_) => eprintln!("The endless mode cannot be scripted."),
+                None => eprintln!("Incorrect game mode."),
+            }
+            return ExitCode::FAILURE;
+        }
+        Ok(args::Command::Bot { index, seed }) => {
+            // on fait jouer un robot externe par des lignes JSON.
+            match config.game_modes.iter().find(|&mode| mode.index == index) {
+                Some(mode) if !mode.endless => {
+                    protocol::run(mode.board_info(), seed);
+                    return ExitCode::SUCCESS;
+                }
+                Some(_) => eprintln!("The endless mode cannot be played by a bot."),
+                None => eprintln!("Incorrect game mode."),
+            }
+            return ExitCode::FAILURE;
+        }
+        Ok(args::Command::Simulate(options)) => {
+            // on lance la simulation en lot, sans affichage du plateau.
+            if let Err(err) = simulation::run(&config.game_modes, &options) {
+                eprintln!("{}", err);
+                return ExitCode::FAILURE;
+            }
+            return ExitCode::SUCCESS;
+        }
+        Ok(args::Command::Bench { board_size, num_mines, seed }) => {
+            // on compare l'ancienne et la nouvelle représentation du plateau.
+            bench::run(board_size, num_mines, seed);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            // on affiche l'erreur et l'aide en cas d'arguments invalides.
+            eprintln!("{}\n\n{}", err, args::usage());
+            return ExitCode::FAILURE;
+        }
     };
+
+    // IA : on trouve l'index maximal parmi les modes de jeu définis dans la configuration.
+    let max_index: u32 = config.game_modes.iter().map(|mode| mode.index).max().unwrap_or_default();
     
     while is_game_on {
         // on affiche le menu et recueille l'entrée du joueur.
-        user_menu_input = display::print_menu();
+        user_menu_input = display::print_menu(&config);
         
-        if user_menu_input <= max_index {
+        // l'option 9 (quitter) est traitée à part, même si des modes ont un index plus grand.
+        if user_menu_input != 9 && user_menu_input <= max_index {
             if user_menu_input == 1 {
                 // on affiche les règles du jeu.
                 display::print_game_rule();
@@ -467,14 +1461,33 @@ pub fn main_game() {
                 match selected_mode {
                     Some(mode) => {
                         // on crée une structure BoardInfo pour stocker les détails du mode de jeu.
-                        let board_info = BoardInfo {
-                            board_size: mode.board_size as usize,
-                            num_mines: mode.num_mines as usize,
-                        };
+                        let board_info = mode.board_info();
                         // on entre dans une boucle pour jouer et permettre au joueur de rejouer.
+                        let mut retry = None;
                         loop {
                             // on commence une partie avec les informations du mode actuel.
-                            play(board_info);
+                            let next = if mode.endless {
+                                endless::play(board_info.board_size, board_info.num_mines)
+                            } else if mode.time_attack > 0 {
+                                time_attack(board_info.clone(), mode.time_attack, notation)
+                            } else {
+                                play(board_info.clone(), notation, retry.take())
+                            };
+
+                            // le joueur a pu quitter la partie en choisissant déjà la suite.
+                            match next {
+                                NextStep::Ask => {}
+                                NextStep::Restart => continue,
+                                NextStep::Retry(seed, assisted) => {
+                                    retry = Some((seed, assisted));
+                                    continue;
+                                }
+                                NextStep::Menu => break,
+                                NextStep::Quit => {
+                                    is_game_on = false;
+                                    break;
+                                }
+                            }
 
                             // on demande au joueur s'il souhaite rejouer.
                             user_play_again = play_again();
@@ -503,4 +1516,91 @@ pub fn main_game() {
             eprintln!("Invalid choice. Please try again.");
         }
     }
-}
\ No newline at end of file
+    ExitCode::SUCCESS
+}
+
+/*
+ *  Tests des déplacements du curseur et des drapeaux
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jump(game: &mut Minesweeper, position: Position) -> (usize, usize) {
+        game.player_message = None;
+        let (row, col) = game.resolve(position).unwrap();
+        game.jump_to(row, col);
+        game.cursor
+    }
+
+    #[test]
+    fn jump_targets_on_the_board_move_the_cursor() {
+        let mut game = Minesweeper::with_seed(BoardInfo::classic(1000, 100), 1);
+        assert_eq!(jump(&mut game, Position { layer: None, row: 999, col: 0 }), (999, 0));
+        assert_eq!(jump(&mut game, Position { layer: None, row: 0, col: 999 }), (0, 999));
+        assert!(game.player_message.is_none());
+    }
+
+    #[test]
+    fn jump_targets_off_the_board_keep_the_cursor() {
+        let mut game = Minesweeper::with_seed(BoardInfo::classic(1000, 100), 1);
+        jump(&mut game, Position { layer: None, row: 500, col: 500 });
+        assert_eq!(jump(&mut game, Position { layer: None, row: 1000, col: 0 }), (500, 500));
+        assert!(game.player_message.is_some());
+        assert_eq!(jump(&mut game, Position { layer: None, row: 0, col: 1000 }), (500, 500));
+        assert!(game.player_message.is_some());
+    }
+
+    #[test]
+    fn jump_targets_in_a_hole_of_the_mask_keep_the_cursor() {
+        let mut board_info = BoardInfo::classic(3, 1);
+        board_info.mask = Some(vec![true, true, true, true, false, true, true, true, true]);
+        let mut game = Minesweeper::with_seed(board_info, 1);
+        jump(&mut game, Position { layer: None, row: 0, col: 2 });
+        assert_eq!(jump(&mut game, Position { layer: None, row: 1, col: 1 }), (0, 2));
+        assert!(game.player_message.is_some());
+        assert_eq!(jump(&mut game, Position { layer: None, row: 2, col: 2 }), (2, 2));
+    }
+
+    #[test]
+    fn marking_without_flags_left_is_refused_even_with_question_marks() {
+        let mut board_info = BoardInfo::classic(8, 10);
+        board_info.question_marks = true;
+        let mut game = Minesweeper::with_seed(board_info, 1);
+        game.apply_action(Action::Reveal(0, 0));
+        let (row, col) = (0..8).flat_map(|row| (0..8).map(move |col| (row, col)))
+            .find(|&(row, col)| game.cell(row, col) == Cell::Undiscovered)
+            .unwrap();
+
+        // tous les drapeaux sont posés : la case reste vierge, et le joueur est prévenu.
+        game.num_mark = 10;
+        game.apply_action(Action::Mark(row, col));
+        assert_eq!(game.cell(row, col), Cell::Undiscovered);
+        assert_eq!(game.player_message.as_deref(), Some("There are no flags left..."));
+        // un marquage refusé n'est ni compté ni retenu.
+        assert_eq!((game.moves, game.actions.len()), (1, 1));
+
+        // avec un drapeau disponible, le cycle continue : drapeau, point d'interrogation, rien.
+        game.num_mark = 9;
+        game.apply_action(Action::Mark(row, col));
+        assert_eq!(game.cell(row, col), Cell::Mark(1));
+        game.apply_action(Action::Mark(row, col));
+        assert_eq!(game.cell(row, col), Cell::Question);
+        game.apply_action(Action::Mark(row, col));
+        assert_eq!(game.cell(row, col), Cell::Undiscovered);
+    }
+
+    #[test]
+    fn revealing_a_flagged_square_is_refused_with_its_own_message() {
+        let mut game = Minesweeper::with_seed(BoardInfo::classic(8, 10), 1);
+        game.apply_action(Action::Reveal(0, 0));
+        let (row, col) = (0..8).flat_map(|row| (0..8).map(move |col| (row, col)))
+            .find(|&(row, col)| game.cell(row, col) == Cell::Undiscovered)
+            .unwrap();
+        game.apply_action(Action::Mark(row, col));
+        game.apply_action(Action::Reveal(row, col));
+        assert_eq!(game.cell(row, col), Cell::Mark(1));
+        assert_eq!(game.player_message.as_deref(), Some("This square is flagged : remove the flag before revealing it..."));
+        assert_eq!(game.moves, 2);
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ *      Nom du fichier : args.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::thread;
+use std::time::Duration;
+
+use super::command::Notation;
+use super::script::ScriptOptions;
+use super::simulation::SimulationOptions;
+
+/*
+ *  Définition des constantes
+ */
+// délai par défaut entre deux coups du robot en mode pas à pas.
+pub(crate) const DEFAULT_AUTOPLAY_DELAY_MS: u64 = 300;
+
+/*
+ *  Définition des Énumérations
+ */
+// Commande demandée sur la ligne de commande.
+pub(crate) enum Command {
+    Menu { notation: Notation },
+    Autoplay { index: u32, delay: Option<Duration> },
+    Script(ScriptOptions),
+    Bot { index: u32, seed: Option<u64> },
+    Simulate(SimulationOptions),
+    Bench { board_size: usize, num_mines: usize, seed: u64 },
+}
+
+/*
+ *  Fonction qui renvoie l'aide de la ligne de commande
+ */
+pub(crate) fn usage() -> String {
+    [
+        "Usage :",
+        "  projet_tb_jm_prog_sure_efficace [--letters]             play with the menu",
+        "      --letters       name the columns with letters (e.g., 'C12' for row 12, column 2)",
+        "  projet_tb_jm_prog_sure_efficace autoplay <mode> [opts]  watch the bot play a game mode",
+        "      --instant       play instantly and only show the final board",
+        "      --delay <ms>    delay between two moves (default 300)",
+        "  projet_tb_jm_prog_sure_efficace script <mode> [opts]    play the moves of a script, without interaction",
+        "      --file <path>   read the moves from a file (default: standard input)",
+        "      --seed <s>      seed of the board (default: random)",
+        "      --verbose       print the board after each move, not only at the end",
+        "      exit status : 0 won, 1 lost, 2 invalid script, 3 unfinished game",
+        "  projet_tb_jm_prog_sure_efficace bot <mode> [opts]       play with JSON lines on the standard input and output",
+        "      --seed <s>      seed of the board (default: random)",
+        "  projet_tb_jm_prog_sure_efficace simulate [opts]         play seeded games with the bot and report statistics",
+        "      --games <n>     number of games per mode (default 100)",
+        "      --seed <s>      seed of the first game (default 0)",
+        "      --threads <n>   number of threads (default: available cores)",
+        "      --mode <index>  game mode to simulate, repeatable (default: all up to 100x100 squares)",
+        "      --json          print the statistics as JSON instead of a table",
+        "  projet_tb_jm_prog_sure_efficace bench [opts]            compare the board representations",
+        "      --size <n>      board size, at least 1 (default 1000)",
+        "      --mines <n>     number of mines, at most size x size (default 15% of the cells)",
+        "      --seed <s>      seed of the mine positions (default 0)",
+    ]
+    .join("\n")
+}
+
+/*
+ *  Fonction qui convertit une valeur numérique d'option
+ */
+fn parse_number<T: std::str::FromStr>(name: &str, value: Option<&String>) -> Result<T, String> {
+    match value {
+        Some(value) => value.parse().map_err(|_| format!("Invalid value '{}' for {}.", value, name)),
+        None => Err(format!("Missing value for {}.", name)),
+    }
+}
+
+/*
+ *  Fonction qui analyse les arguments de la ligne de commande (sans le nom du programme)
+ */
+pub(crate) fn parse_args(args: &[String]) -> Result<Command, String> {
+    // sans argument, on lance le menu habituel.
+    let Some(command) = args.first() else {
+        return Ok(Command::Menu { notation: Notation::Numeric });
+    };
+
+    match command.as_str() {
+        "--letters" if args.len() == 1 => Ok(Command::Menu { notation: Notation::Spreadsheet }),
+        "autoplay" => {
+            // on lit l'index du mode de jeu puis les options.
+            let index = parse_number("<mode>", args.get(1))?;
+            let mut delay = Some(Duration::from_millis(DEFAULT_AUTOPLAY_DELAY_MS));
+            let mut options = args[2..].iter();
+            while let Some(option) = options.next() {
+                match option.as_str() {
+                    "--instant" => delay = None,
+                    "--delay" => delay = Some(Duration::from_millis(parse_number("--delay", options.next())?)),
+                    other => return Err(format!("Unknown option '{}'.", other)),
+                }
+            }
+            Ok(Command::Autoplay { index, delay })
+        }
+        "script" => {
+            // on lit l'index du mode de jeu puis les options.
+            let mut options = ScriptOptions { index: parse_number("<mode>", args.get(1))?, file: None, seed: None, verbose: false };
+            let mut arguments = args[2..].iter();
+            while let Some(option) = arguments.next() {
+                match option.as_str() {
+                    "--file" => options.file = Some(arguments.next().ok_or("Missing value for --file.")?.clone()),
+                    "--seed" => options.seed = Some(parse_number("--seed", arguments.next())?),
+                    "--verbose" => options.verbose = true,
+                    other => return Err(format!("Unknown option '{}'.", other)),
+                }
+            }
+            Ok(Command::Script(options))
+        }
+        "bot" => {
+            // on lit l'index du mode de jeu puis les options.
+            let index = parse_number("<mode>", args.get(1))?;
+            let mut seed = None;
+            let mut arguments = args[2..].iter();
+            while let Some(option) = arguments.next() {
+                match option.as_str() {
+                    "--seed" => seed = Some(parse_number("--seed", arguments.next())?),
+                    other => return Err(format!("Unknown option '{}'.", other)),
+                }
+            }
+            Ok(Command::Bot { index, seed })
+        }
+        "simulate" => {
+            // on part des valeurs par défaut puis on lit les options.
+            let mut options = SimulationOptions {
+                games: 100,
+                seed: 0,
+                threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+                modes: Vec::new(),
+                json: false,
+            };
+            let mut arguments = args[1..].iter();
+            while let Some(option) = arguments.next() {
+                match option.as_str() {
+                    "--games" => options.games = parse_number("--games", arguments.next())?,
+                    "--seed" => options.seed = parse_number("--seed", arguments.next())?,
+                    "--threads" => options.threads = parse_number("--threads", arguments.next())?,
+                    "--mode" => options.modes.push(parse_number("--mode", arguments.next())?),
+                    "--json" => options.json = true,
+                    other => return Err(format!("Unknown option '{}'.", other)),
+                }
+            }
+            Ok(Command::Simulate(options))
+        }
+        "bench" => {
+            let (mut board_size, mut num_mines, mut seed): (usize, Option<usize>, u64) = (1000, None, 0);
+            let mut arguments = args[1..].iter();
+            while let Some(option) = arguments.next() {
+                match option.as_str() {
+                    "--size" => board_size = parse_number("--size", arguments.next())?,
+                    "--mines" => num_mines = Some(parse_number("--mines", arguments.next())?),
+                    "--seed" => seed = parse_number("--seed", arguments.next())?,
+                    other => return Err(format!("Unknown option '{}'.", other)),
+                }
+            }
+            // un plateau vide n'a pas de case à mesurer.
+            if board_size == 0 {
+                return Err("The board size must be at least 1.".to_string());
+            }
+            let cells = board_size.checked_mul(board_size).ok_or(format!("The board size {} is too large.", board_size))?;
+            // par défaut, on pose des mines sur 15 % des cases.
+            let num_mines = num_mines.unwrap_or(cells.saturating_mul(15) / 100);
+            if num_mines > cells {
+                return Err(format!("A {}x{} board cannot hold {} mines (at most {}).", board_size, board_size, num_mines, cells));
+            }
+            Ok(Command::Bench { board_size, num_mines, seed })
+        }
+        other => Err(format!("Unknown command '{}'.", other)),
+    }
+}
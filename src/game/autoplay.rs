@@ -0,0 +1,134 @@
+/*
+ *      Nom du fichier : autoplay.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::thread;
+use std::time::Duration;
+
+use crate::game::{Action, BoardInfo, GameActions, Minesweeper};
+use super::{display, solver};
+
+/*
+ *  Définition des Structures
+ */
+// Bilan d'une partie jouée (ou terminée) par le robot.
+pub(crate) struct AutoplayReport {
+    pub(crate) won: bool,
+    pub(crate) guesses: usize,
+    pub(crate) moves: usize,
+}
+
+/*
+ *  Fonction qui applique une action du robot et l'affiche si besoin
+ */
+fn play_action(game: &mut Minesweeper, action: Action, delay: Option<Duration>, report: &mut AutoplayReport) -> Vec<(usize, usize)> {
+    let revealed = game.apply_action(action);
+    report.moves += 1;
+
+    // en mode pas à pas, on affiche le plateau puis on attend avant le coup suivant.
+    if let Some(delay) = delay {
+        display::print_board(game);
+        thread::sleep(delay);
+    }
+    revealed
+}
+
+/*
+ *  Fonction qui révèle une case, en retirant d'abord un éventuel drapeau du joueur
+ */
+fn reveal_cell(game: &mut Minesweeper, (row, col): (usize, usize), delay: Option<Duration>, report: &mut AutoplayReport) -> Vec<(usize, usize)> {
+    // avec plusieurs mines par case, les drapeaux s'ajoutent jusqu'au maximum avant d'être tous retirés.
+    while game.grid.is_flagged(row, col) {
+        play_action(game, Action::Mark(row, col), delay, report);
+    }
+    play_action(game, Action::Reveal(row, col), delay, report)
+}
+
+/*
+ *  Fonction qui laisse le robot jouer jusqu'à la fin de la partie
+ */
+pub(crate) fn finish_game(game: &mut Minesweeper, delay: Option<Duration>) -> AutoplayReport {
+    let mut report = AutoplayReport { won: false, guesses: 0, moves: 0 };
+
+    // cases révélées par les derniers coups, autour desquelles on cherche d'abord des déductions.
+    let mut recent = Vec::new();
+
+    // le premier coup est toujours sûr : on commence au centre du plateau.
+    if !game.first_play {
+        let (row, col) = game.grid.center();
+        recent = play_action(game, Action::Reveal(row, col), delay, &mut report);
+    }
+
+    while !game.game_over && !game.check_win() {
+        // on analyse d'abord les alentours des derniers coups, puis tout le plateau si cela ne suffit pas.
+        let analysis = Some(solver::analyse_around(game, &recent))
+            .filter(|analysis| !analysis.safe.is_empty())
+            .unwrap_or_else(|| solver::analyse(game));
+        recent.clear();
+
+        // on pose un drapeau sur les mines certaines (pour l'affichage).
+        for &(row, col) in &analysis.mines {
+            if !game.grid.is_flagged(row, col) && game.num_mark < game.configuration.num_mines {
+                play_action(game, Action::Mark(row, col), delay, &mut report);
+            }
+        }
+
+        if !analysis.safe.is_empty() {
+            // on révèle toutes les cases déduites comme sûres.
+            for &cell in &analysis.safe {
+                if game.game_over || game.check_win() {
+                    break;
+                }
+                if !game.grid.is_revealed(cell.0, cell.1) {
+                    recent.extend(reveal_cell(game, cell, delay, &mut report));
+                }
+            }
+        } else if let Some((cell, _)) = analysis.best_guess {
+            // aucune déduction possible : on tente la case la moins risquée.
+            report.guesses += 1;
+            recent.extend(reveal_cell(game, cell, delay, &mut report));
+        } else {
+            // il ne reste aucune case à jouer.
+            break;
+        }
+    }
+
+    report.won = !game.game_over && game.check_win();
+    report
+}
+
+/*
+ *  Fonction qui fait jouer une partie complète au robot
+ */
+pub fn play(game_config: BoardInfo, delay: Option<Duration>) -> AutoplayReport {
+    // on déclare une nouvelle instance du jeu Minesweeper.
+    let mut game_instance = Minesweeper::new(game_config);
+
+    // on laisse le robot jouer toute la partie.
+    let report = finish_game(&mut game_instance, delay);
+
+    // on affiche le plateau final et le bilan du robot.
+    game_instance.player_message = Some(report_message(&report));
+    display::print_board(&mut game_instance);
+    report
+}
+
+/*
+ *  Fonction qui formate le bilan du robot pour le joueur
+ */
+pub(crate) fn report_message(report: &AutoplayReport) -> String {
+    format!(
+        "The bot {} after {} move(s) and needed {} guess(es).",
+        if report.won { "won" } else { "hit a mine" },
+        report.moves,
+        report.guesses
+    )
+}
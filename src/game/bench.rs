@@ -0,0 +1,168 @@
+/*
+ *      Nom du fichier : bench.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::collections::HashSet;
+use std::mem::size_of;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::SeedableRng;
+
+use crate::game::{BoardInfo, GameActions, Minesweeper};
+
+/*
+ *  Définition des constantes
+ */
+// taille de pile du fil qui exécute l'ancienne révélation récursive (1 Gio).
+const LEGACY_STACK_SIZE: usize = 1 << 30;
+
+/*
+ *  Définition des Structures
+ */
+// Ancienne représentation du plateau (ensembles de coordonnées), gardée pour comparaison.
+struct LegacyBoard {
+    size: usize,
+    mines: HashSet<(usize, usize)>,
+    revealed: HashSet<(usize, usize)>,
+}
+
+/*
+ *  Implémentation de l'ancienne représentation
+ */
+impl LegacyBoard {
+    fn count_mines_around(&self, row: usize, col: usize) -> u8 {
+        let mut count = 0;
+        for r in row.saturating_sub(1)..=row + 1 {
+            for c in col.saturating_sub(1)..=col + 1 {
+                if r < self.size && c < self.size && self.mines.contains(&(r, c)) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn reveal(&mut self, row: usize, col: usize) {
+        if row >= self.size || col >= self.size || self.revealed.contains(&(row, col)) || self.mines.contains(&(row, col)) {
+            return;
+        }
+        self.revealed.insert((row, col));
+        if self.count_mines_around(row, col) == 0 {
+            for r in row.saturating_sub(1)..=row + 1 {
+                for c in col.saturating_sub(1)..=col + 1 {
+                    self.reveal(r, c);
+                }
+            }
+        }
+    }
+
+    fn memory(&self) -> usize {
+        // on estime la place occupée par les entrées des tables de hachage (clé + octet de contrôle).
+        (self.mines.capacity() + self.revealed.capacity()) * (size_of::<(usize, usize)>() + 1)
+    }
+}
+
+/*
+ *  Fonction qui mesure la durée d'une opération
+ */
+fn measure(operation: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    operation();
+    start.elapsed()
+}
+
+/*
+ *  Fonction qui affiche une ligne de comparaison
+ */
+fn print_line(label: &str, legacy: Duration, dense: Duration) {
+    println!(
+        "{:<28} {:>12.2} {:>12.2} {:>9.1}x",
+        label,
+        legacy.as_secs_f64() * 1000.0,
+        dense.as_secs_f64() * 1000.0,
+        legacy.as_secs_f64() / dense.as_secs_f64().max(f64::EPSILON)
+    );
+}
+
+/*
+ *  Fonction qui compare les deux représentations sur un même plateau
+ */
+pub(crate) fn run(board_size: usize, num_mines: usize, seed: u64) {
+    // on tire une seule fois les positions des mines pour les deux représentations.
+    let num_mines = num_mines.min(board_size * board_size);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let positions: Vec<(usize, usize)> = index::sample(&mut rng, board_size * board_size, num_mines)
+        .into_iter()
+        .map(|cell| (cell / board_size, cell % board_size))
+        .collect();
+
+    let mut legacy = LegacyBoard { size: board_size, mines: HashSet::new(), revealed: HashSet::new() };
+    let mut dense = Minesweeper::with_seed(BoardInfo::classic(board_size, num_mines), seed);
+    dense.first_play = true;
+
+    println!("Board {}x{} with {} mines\n", board_size, board_size, num_mines);
+    println!("{:<28} {:>12} {:>12} {:>10}", "Operation", "HashSet (ms)", "Dense (ms)", "Speedup");
+
+    // 1. pose des mines (et, pour le plateau dense, calcul des compteurs de voisins).
+    let legacy_time = measure(|| legacy.mines.extend(positions.iter().copied()));
+    let dense_time = measure(|| positions.iter().for_each(|&(row, col)| dense.grid.place_mine(row, col)));
+    print_line("Place mines", legacy_time, dense_time);
+
+    // 2. lecture du nombre de mines voisines de chaque case.
+    let mut checksums = (0u64, 0u64);
+    let legacy_time = measure(|| {
+        for cell in 0..board_size * board_size {
+            checksums.0 += legacy.count_mines_around(cell / board_size, cell % board_size) as u64;
+        }
+    });
+    let dense_time = measure(|| {
+        for cell in 0..board_size * board_size {
+            checksums.1 += dense.count_mines_around(cell / board_size, cell % board_size) as u64;
+        }
+    });
+    print_line("Count mines around (all)", legacy_time, dense_time);
+    // l'ancien calcul compte aussi la case elle-même lorsqu'elle est minée.
+    assert_eq!(checksums.0, checksums.1 + num_mines as u64, "both representations must agree");
+
+    // 3. révélation de toutes les cases sûres (avec propagation des zones vides).
+    // l'ancienne version est récursive : on lui donne une grande pile pour les plateaux peu minés.
+    let legacy_time = thread::scope(|scope| {
+        thread::Builder::new()
+            .stack_size(LEGACY_STACK_SIZE)
+            .spawn_scoped(scope, || measure(|| {
+                for cell in 0..board_size * board_size {
+                    legacy.reveal(cell / board_size, cell % board_size);
+                }
+            }))
+            .expect("failed to spawn the legacy reveal thread")
+            .join()
+            .expect("legacy reveal thread panicked")
+    });
+    let dense_time = measure(|| {
+        for cell in 0..board_size * board_size {
+            dense.reveal(cell / board_size, cell % board_size);
+        }
+    });
+    print_line("Reveal every safe cell", legacy_time, dense_time);
+    assert_eq!(legacy.revealed.len(), dense.num_revealed, "both representations must agree");
+
+    // on compare enfin la mémoire occupée par l'état du plateau.
+    let dense_memory = 2 * board_size * board_size;
+    println!(
+        "\n{:<28} {:>12} {:>12}",
+        "Approx. memory (KiB)",
+        legacy.memory() / 1024,
+        dense_memory / 1024
+    );
+}
@@ -0,0 +1,120 @@
+/*
+ *      Nom du fichier : clues.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+
+/*
+ *  Définition des Énumérations
+ */
+// Règle qui transforme le vrai nombre de mines autour d'une case en chiffre affiché.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ClueRule {
+    // le chiffre est exact (jeu classique).
+    #[default]
+    Exact,
+    // le chiffre ment d'exactement un, vers le haut ou vers le bas.
+    Liar,
+    // le chiffre ne donne que la parité du nombre de mines (0 ou 1).
+    Parity,
+}
+
+/*
+ *  Implémentation des règles d'affichage des chiffres
+ */
+impl ClueRule {
+    /*
+     *  Fonction qui calcule le chiffre affiché à partir du vrai nombre de mines (au plus `max`)
+     */
+    pub(crate) fn distort(self, count: usize, max: usize, rng: &mut impl Rng) -> u8 {
+        let shown = match self {
+            ClueRule::Exact => count,
+            // le menteur ne peut pas descendre sous 0 ni dépasser le maximum possible.
+            ClueRule::Liar if count == 0 => 1,
+            ClueRule::Liar if count == max => count - 1,
+            ClueRule::Liar if rng.gen_bool(0.5) => count + 1,
+            ClueRule::Liar => count - 1,
+            ClueRule::Parity => count % 2,
+        };
+        shown as u8
+    }
+
+    /*
+     *  Fonction qui renvoie les nombres de mines compatibles avec un chiffre affiché (entre 0 et `max`)
+     */
+    pub(crate) fn possible_counts(self, shown: usize, max: usize) -> Vec<usize> {
+        match self {
+            ClueRule::Exact => vec![shown],
+            ClueRule::Liar => [shown.checked_sub(1), Some(shown + 1)].into_iter().flatten().collect(),
+            ClueRule::Parity => (shown..=max).step_by(2).collect(),
+        }
+        .into_iter()
+        .filter(|&count| count <= max)
+        .collect()
+    }
+}
+
+/*
+ *  Tests des règles d'affichage des chiffres
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const RULES: [ClueRule; 3] = [ClueRule::Exact, ClueRule::Liar, ClueRule::Parity];
+
+    #[test]
+    fn shown_clues_always_allow_the_true_count() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for rule in RULES {
+            for max in 1..=8 {
+                for count in 0..=max {
+                    for _ in 0..20 {
+                        let shown = rule.distort(count, max, &mut rng) as usize;
+                        assert!(rule.possible_counts(shown, max).contains(&count), "{:?} : {} shown for {} (max {})", rule, shown, count, max);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exact_clues_are_the_count() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(ClueRule::Exact.distort(3, 8, &mut rng), 3);
+        assert_eq!(ClueRule::Exact.possible_counts(3, 8), [3]);
+    }
+
+    #[test]
+    fn liars_are_off_by_one_within_the_bounds() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(ClueRule::Liar.distort(0, 8, &mut rng), 1);
+        assert_eq!(ClueRule::Liar.distort(8, 8, &mut rng), 7);
+        for _ in 0..20 {
+            assert!([2, 4].contains(&ClueRule::Liar.distort(3, 8, &mut rng)));
+        }
+        assert_eq!(ClueRule::Liar.possible_counts(0, 8), [1]);
+        assert_eq!(ClueRule::Liar.possible_counts(3, 8), [2, 4]);
+        assert_eq!(ClueRule::Liar.possible_counts(8, 8), [7]);
+    }
+
+    #[test]
+    fn parity_clues_allow_every_count_of_the_same_parity() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(ClueRule::Parity.distort(5, 8, &mut rng), 1);
+        assert_eq!(ClueRule::Parity.possible_counts(0, 8), [0, 2, 4, 6, 8]);
+        assert_eq!(ClueRule::Parity.possible_counts(1, 6), [1, 3, 5]);
+    }
+}
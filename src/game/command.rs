@@ -0,0 +1,544 @@
+/*
+ *      Nom du fichier : command.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Grammaire des commandes du joueur (sans distinction de majuscules, plusieurs commandes par ligne séparées par des espaces) :
+ *
+ *      ligne     := commande { commande }
+ *      commande  := zone | zone '!' | mot-clé [zone]
+ *      zone      := plage ',' plage [',' plage]          (ligne,colonne ou couche,ligne,colonne)
+ *                 | case ['-' case]                      (notation de tableur, colonne en lettres : 'C12', 'B2-D4')
+ *      plage     := nombre | nombre '-' nombre          (une plage désigne toutes les cases entre ses bornes)
+ *      case      := lettres nombre
+ *      mot-clé   := 'reveal' | 'flag' | 'chord' | 'goto'                      (suivis d'une zone, une seule case pour 'goto')
+ *                 | 'hint' | 'undo' | 'save' | 'help' | 'auto'               (sans argument)
+ *                 | 'restart' | 'retry' | 'menu' | 'quit'                    (pour quitter la partie)
+ *                 | 'w' | 'a' | 's' | 'd' | 'q' | 'e'                         (défilement, couches)
+ *
+ *  En mode infini, les positions sont signées ('-2,0', '12,-19') et ne désignent qu'une case (pas de plage).
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::fmt;
+
+/*
+ *  Définition des constantes
+ */
+// nombre maximal de cases désignées par une seule zone (pour qu'une faute de frappe ne joue pas tout le plateau).
+const MAX_AREA_SQUARES: usize = 400;
+// liste des commandes affichée par 'help'.
+pub(crate) const HELP: &str = "Commands :
+  row,col          reveal a square (e.g., '2,0'), or 'layer,row,col' on 3D boards
+  row,col!         flag a square (e.g., '3,3!'), again to remove the flag
+  C12, C12!        same with the column in letters (here column 2, row 12)
+  2-4,5!, B2-D4    ranges act on every square between their bounds
+  reveal row,col   same as 'row,col'
+  flag row,col     same as 'row,col!'
+  chord row,col    reveal the neighbours of a number surrounded by as many flags
+  goto row,col     move the view to a square
+  w a s d          scroll the view, 'q' and 'e' to switch layers on 3D boards
+  hint             show a safe square, a sure mine, or the least risky guess
+  undo             take back the last move
+  save             save the game, to resume it from the menu
+  auto             let the bot finish the game
+  restart          give up the game and start a new board of the same mode
+  retry            give up the game and start the same board again
+  menu             give up the game and go back to the menu
+  quit             give up the game and leave
+  help             show this list
+Several commands can be entered on one line (e.g., '3,4 5,6 7,7!') : they are played in order,
+until one of them ends the game.";
+
+/*
+ *  Définition des Structures et des Énumérations
+ */
+// Case désignée par le joueur, telle qu'il l'a saisie (la couche est facultative, les coordonnées signées en mode infini).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position<T = usize> {
+    pub(crate) layer: Option<usize>,
+    pub(crate) row: T,
+    pub(crate) col: T,
+}
+
+// Commande du joueur pendant une partie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Command<T = usize> {
+    Reveal(Position<T>),
+    Flag(Position<T>),
+    Chord(Position<T>),
+    Goto(Position<T>),
+    // défilement d'un pas (en lignes et en colonnes) et changement de couche en 3D.
+    Scroll(isize, isize),
+    Layer(isize),
+    Hint,
+    Undo,
+    Save,
+    Restart,
+    Retry,
+    Menu,
+    Quit,
+    Help,
+    Auto,
+}
+
+// Façon d'écrire les cases : 'ligne,colonne', ou colonne en lettres comme dans un tableur ('C12').
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Notation {
+    #[default]
+    Numeric,
+    Spreadsheet,
+}
+
+// Erreur de saisie, avec de quoi expliquer au joueur ce qui ne va pas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ParseError {
+    Empty,
+    UnknownCommand(String),
+    MissingPosition(String),
+    SingleSquareOnly(String),
+    AreaTooLarge(usize),
+    MissingNumber,
+    InvalidNumber(String),
+    InvalidSquare(String),
+    WrongNumberCount(usize),
+    // deux nombres séparés par un espace au lieu d'une virgule ('3 3').
+    MissingComma(String, String),
+    MisplacedBang,
+}
+
+/*
+ *  Implémentation de l'affichage des erreurs de saisie
+ */
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Please enter a command (type 'help' to list them)..."),
+            ParseError::UnknownCommand(word) => write!(f, "Unknown command '{}' (type 'help' to list the commands)...", word),
+            ParseError::MissingPosition(word) => write!(f, "'{}' needs a position, e.g. '{} 3,4'...", word, word),
+            ParseError::SingleSquareOnly(word) => write!(f, "'{}' takes a single square, not a range...", word),
+            ParseError::AreaTooLarge(count) => write!(f, "A range covers at most {} squares, not {}...", MAX_AREA_SQUARES, count),
+            ParseError::MissingNumber => write!(f, "A number is missing around a comma : positions are written 'row,col'..."),
+            ParseError::InvalidNumber(part) => write!(f, "'{}' is not a valid row or column (positions are written 'row,col')...", part),
+            ParseError::InvalidSquare(square) => write!(f, "'{}' is not a valid square (a column in letters then a row, e.g. 'C12')...", square),
+            ParseError::WrongNumberCount(count) => write!(f, "A position has 2 numbers ('row,col') or 3 on 3D boards ('layer,row,col'), not {}...", count),
+            ParseError::MissingComma(first, second) => {
+                write!(f, "A comma is missing in '{} {}' : positions are written '{},{}'...", first, second, first, second)
+            }
+            ParseError::MisplacedBang => write!(f, "'!' goes right after a position to place a flag, e.g. '3,3!'..."),
+        }
+    }
+}
+
+/*
+ *  Implémentation des notations des cases
+ */
+impl Notation {
+    /*
+     *  Fonction qui renvoie le nom d'une colonne ('0', '1', ... ou 'A', 'B', ... 'Z', 'AA', ...)
+     */
+    pub(crate) fn column_label(self, col: usize) -> String {
+        match self {
+            Notation::Numeric => col.to_string(),
+            Notation::Spreadsheet => {
+                let mut letters = Vec::new();
+                let mut rest = col + 1;
+                while rest > 0 {
+                    letters.push(b'A' + ((rest - 1) % 26) as u8);
+                    rest = (rest - 1) / 26;
+                }
+                letters.iter().rev().map(|&letter| letter as char).collect()
+            }
+        }
+    }
+
+    /*
+     *  Fonction qui écrit une case comme le joueur la saisit
+     */
+    pub(crate) fn square(self, row: usize, col: usize) -> String {
+        match self {
+            Notation::Numeric => format!("{},{}", row, col),
+            Notation::Spreadsheet => format!("{}{}", self.column_label(col), row),
+        }
+    }
+}
+
+/*
+ *  Fonction qui indique si un mot est une case en notation de tableur (des lettres suivies d'un nombre)
+ */
+fn is_square(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic()) && word.contains(|c: char| c.is_ascii_digit())
+}
+
+/*
+ *  Fonction qui lit une case en notation de tableur ('c12' : colonne 2, ligne 12)
+ */
+fn parse_square(text: &str) -> Result<(usize, usize), ParseError> {
+    let invalid = || ParseError::InvalidSquare(text.to_uppercase());
+    let split = text.find(|c: char| !c.is_ascii_lowercase()).ok_or_else(invalid)?;
+    let (letters, row) = text.split_at(split);
+    // les colonnes se comptent comme dans un tableur : 'a' à 'z', puis 'aa', 'ab', ...
+    let col = letters.bytes()
+        .try_fold(0usize, |col, letter| col.checked_mul(26)?.checked_add((letter - b'a') as usize + 1))
+        .ok_or_else(invalid)?;
+    match row.parse::<usize>() {
+        Ok(row) if !letters.is_empty() => Ok((row, col - 1)),
+        _ => Err(invalid()),
+    }
+}
+
+/*
+ *  Fonction qui lit une zone en notation de tableur ('c12' ou le rectangle 'b2-d4')
+ */
+fn parse_square_area(text: &str) -> Result<Vec<Position>, ParseError> {
+    if text.contains('!') {
+        return Err(ParseError::MisplacedBang);
+    }
+    let (first, last) = match text.split_once('-') {
+        Some((first, last)) => (parse_square(first)?, parse_square(last)?),
+        None => (parse_square(text)?, parse_square(text)?),
+    };
+    let (rows, cols) = ((first.0.min(last.0), first.0.max(last.0)), (first.1.min(last.1), first.1.max(last.1)));
+    let count = (rows.1 - rows.0).saturating_add(1).saturating_mul((cols.1 - cols.0).saturating_add(1));
+    if count > MAX_AREA_SQUARES {
+        return Err(ParseError::AreaTooLarge(count));
+    }
+    Ok((rows.0..=rows.1).flat_map(|row| (cols.0..=cols.1).map(move |col| Position { layer: None, row, col })).collect())
+}
+
+/*
+ *  Fonction qui lit une zone dans l'une ou l'autre des notations
+ */
+fn parse_any_area(text: &str) -> Result<Vec<Position>, ParseError> {
+    if is_square(text) {
+        parse_square_area(text)
+    } else {
+        parse_area(text)
+    }
+}
+
+/*
+ *  Fonction qui lit une plage 'nombre' ou 'début-fin' (dans un sens ou dans l'autre)
+ */
+fn parse_range(part: &str) -> Result<(usize, usize), ParseError> {
+    let number = |text: &str| text.parse::<usize>().map_err(|_| ParseError::InvalidNumber(part.to_string()));
+    match part.split_once('-') {
+        _ if part.is_empty() => Err(ParseError::MissingNumber),
+        Some((first, last)) => {
+            let (first, last) = (number(first)?, number(last)?);
+            Ok((first.min(last), first.max(last)))
+        }
+        None => number(part).map(|value| (value, value)),
+    }
+}
+
+/*
+ *  Fonction qui lit une zone 'ligne,colonne' ou 'couche,ligne,colonne' et renvoie ses cases
+ */
+fn parse_area(text: &str) -> Result<Vec<Position>, ParseError> {
+    if text.contains('!') {
+        return Err(ParseError::MisplacedBang);
+    }
+    let ranges = text.split(',').map(parse_range).collect::<Result<Vec<(usize, usize)>, ParseError>>()?;
+    let (layers, rows, cols) = match ranges[..] {
+        [rows, cols] => (None, rows, cols),
+        [layers, rows, cols] => (Some(layers), rows, cols),
+        _ => return Err(ParseError::WrongNumberCount(ranges.len())),
+    };
+
+    // on compte les cases avant de les énumérer, une plage pouvant être immense.
+    let count = ranges.iter().fold(1usize, |count, &(first, last)| count.saturating_mul((last - first).saturating_add(1)));
+    if count > MAX_AREA_SQUARES {
+        return Err(ParseError::AreaTooLarge(count));
+    }
+    let layers: Vec<Option<usize>> = match layers {
+        Some((first, last)) => (first..=last).map(Some).collect(),
+        None => vec![None],
+    };
+    Ok(layers.into_iter()
+        .flat_map(|layer| (rows.0..=rows.1).flat_map(move |row| (cols.0..=cols.1).map(move |col| Position { layer, row, col })))
+        .collect())
+}
+
+/*
+ *  Fonction qui lit une case 'ligne,colonne' aux coordonnées signées (mode infini)
+ */
+fn parse_signed_area(text: &str) -> Result<Vec<Position<i64>>, ParseError> {
+    if text.contains('!') {
+        return Err(ParseError::MisplacedBang);
+    }
+    let number = |part: &str| match part {
+        "" => Err(ParseError::MissingNumber),
+        _ => part.parse::<i64>().map_err(|_| ParseError::InvalidNumber(part.to_string())),
+    };
+    let numbers = text.split(',').map(number).collect::<Result<Vec<i64>, ParseError>>()?;
+    match numbers[..] {
+        [row, col] => Ok(vec![Position { layer: None, row, col }]),
+        _ => Err(ParseError::WrongNumberCount(numbers.len())),
+    }
+}
+
+/*
+ *  Fonction qui recolle les nombres d'une zone écrits avec des espaces (par exemple '12 , 19' ou '3,3 !')
+ */
+fn join_areas(text: &str, ranges: bool) -> String {
+    let mut joined = String::new();
+    for word in text.split_whitespace() {
+        // un '-' en tête de mot ne prolonge une plage qu'après un nombre (sinon c'est un nombre négatif).
+        let glued = joined.ends_with(',') || (ranges && joined.ends_with('-')) || word.starts_with([',', '!'])
+            || (ranges && word.starts_with('-') && joined.ends_with(|c: char| c.is_ascii_digit()));
+        if !joined.is_empty() && !glued {
+            joined.push(' ');
+        }
+        joined.push_str(word);
+    }
+    joined
+}
+
+/*
+ *  Fonction qui indique si un mot est une zone : il commence par un nombre (éventuellement négatif) ou une case de tableur
+ */
+fn is_area(word: &str) -> bool {
+    let digits = word.strip_prefix('-').unwrap_or(word);
+    digits.starts_with(|c: char| c.is_ascii_digit()) || is_square(word)
+}
+
+/*
+ *  Fonction qui convertit une ligne saisie par le joueur en commandes, à jouer dans l'ordre
+ *  (une erreur dans l'une des commandes rejette toute la ligne)
+ */
+pub(crate) fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_with(input, true, parse_any_area)
+}
+
+/*
+ *  Fonction qui convertit une ligne saisie en mode infini, où les positions sont signées
+ */
+pub(crate) fn parse_signed(input: &str) -> Result<Vec<Command<i64>>, ParseError> {
+    parse_with(input, false, parse_signed_area)
+}
+
+/*
+ *  Fonction qui lit les commandes d'une ligne, avec la façon de lire les zones du plateau joué
+ */
+fn parse_with<T: Copy>(input: &str, ranges: bool, parse_area: fn(&str) -> Result<Vec<Position<T>>, ParseError>) -> Result<Vec<Command<T>>, ParseError> {
+    let text = join_areas(&input.to_lowercase(), ranges);
+    if text.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut words = text.split(' ').peekable();
+    let mut commands = Vec::new();
+    while let Some(word) = words.next() {
+        // un mot qui commence par un nombre (ou une case de tableur) est une zone, suivie d'un '!' pour des drapeaux.
+        if is_area(word) {
+            // un nombre seul suivi d'un autre nombre est une position dont la virgule a été oubliée.
+            if !word.contains(',') && !is_square(word) {
+                if let Some(next) = words.next_if(|next| !next.contains(',') && is_area(next) && !is_square(next)) {
+                    return Err(ParseError::MissingComma(word.to_string(), next.to_string()));
+                }
+            }
+            match word.strip_suffix('!') {
+                Some(area) => commands.extend(parse_area(area)?.into_iter().map(Command::Flag)),
+                None => commands.extend(parse_area(word)?.into_iter().map(Command::Reveal)),
+            }
+            continue;
+        }
+
+        // sinon, c'est un mot-clé, suivi de sa zone s'il en prend une.
+        let mut area = || match words.next_if(|next| next.starts_with('-') || is_area(next)) {
+            Some(area) => parse_area(area),
+            None => Err(ParseError::MissingPosition(word.to_string())),
+        };
+        match word {
+            "reveal" => commands.extend(area()?.into_iter().map(Command::Reveal)),
+            "flag" => commands.extend(area()?.into_iter().map(Command::Flag)),
+            "chord" => commands.extend(area()?.into_iter().map(Command::Chord)),
+            "goto" => match area()?[..] {
+                [position] => commands.push(Command::Goto(position)),
+                _ => return Err(ParseError::SingleSquareOnly(word.to_string())),
+            },
+            "hint" => commands.push(Command::Hint),
+            "undo" => commands.push(Command::Undo),
+            "save" => commands.push(Command::Save),
+            "restart" => commands.push(Command::Restart),
+            "retry" => commands.push(Command::Retry),
+            "menu" => commands.push(Command::Menu),
+            "quit" => commands.push(Command::Quit),
+            "help" => commands.push(Command::Help),
+            "auto" => commands.push(Command::Auto),
+            "w" => commands.push(Command::Scroll(-1, 0)),
+            "s" => commands.push(Command::Scroll(1, 0)),
+            "a" => commands.push(Command::Scroll(0, -1)),
+            "d" => commands.push(Command::Scroll(0, 1)),
+            "q" => commands.push(Command::Layer(-1)),
+            "e" => commands.push(Command::Layer(1)),
+            _ if word.starts_with('!') => return Err(ParseError::MisplacedBang),
+            _ => return Err(ParseError::UnknownCommand(word.to_string())),
+        }
+    }
+    Ok(commands)
+}
+
+/*
+ *  Tests du parseur de commandes
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(row: usize, col: usize) -> Position {
+        Position { layer: None, row, col }
+    }
+
+    fn one(command: Command) -> Result<Vec<Command>, ParseError> {
+        Ok(vec![command])
+    }
+
+    #[test]
+    fn positions_reveal_and_flag() {
+        assert_eq!(parse("2,0\n"), one(Command::Reveal(at(2, 0))));
+        assert_eq!(parse("  12 , 19  "), one(Command::Reveal(at(12, 19))));
+        assert_eq!(parse("3,3!"), one(Command::Flag(at(3, 3))));
+        assert_eq!(parse("3, 3 !\r\n"), one(Command::Flag(at(3, 3))));
+        assert_eq!(parse("1,2,3"), one(Command::Reveal(Position { layer: Some(1), row: 2, col: 3 })));
+    }
+
+    #[test]
+    fn keywords_take_positions() {
+        assert_eq!(parse("reveal 4,5"), one(Command::Reveal(at(4, 5))));
+        assert_eq!(parse("FLAG 4,5"), one(Command::Flag(at(4, 5))));
+        assert_eq!(parse("chord\t4,5"), one(Command::Chord(at(4, 5))));
+        assert_eq!(parse("goto 500,250"), one(Command::Goto(at(500, 250))));
+        assert_eq!(parse("flag"), Err(ParseError::MissingPosition("flag".to_string())));
+        assert_eq!(parse("goto   "), Err(ParseError::MissingPosition("goto".to_string())));
+        assert_eq!(parse("flag hint"), Err(ParseError::MissingPosition("flag".to_string())));
+    }
+
+    #[test]
+    fn keywords_without_argument() {
+        assert_eq!(parse("hint"), one(Command::Hint));
+        assert_eq!(parse(" Undo "), one(Command::Undo));
+        assert_eq!(parse("save"), one(Command::Save));
+        assert_eq!(parse("quit"), one(Command::Quit));
+        assert_eq!(parse("Restart"), one(Command::Restart));
+        assert_eq!(parse("retry"), one(Command::Retry));
+        assert_eq!(parse("menu"), one(Command::Menu));
+        assert_eq!(parse("help"), one(Command::Help));
+        assert_eq!(parse("auto"), one(Command::Auto));
+        assert_eq!(parse("w"), one(Command::Scroll(-1, 0)));
+        assert_eq!(parse("e"), one(Command::Layer(1)));
+    }
+
+    #[test]
+    fn empty_and_single_character_input() {
+        assert_eq!(parse(""), Err(ParseError::Empty));
+        assert_eq!(parse("\n"), Err(ParseError::Empty));
+        assert_eq!(parse("   "), Err(ParseError::Empty));
+        assert_eq!(parse("x"), Err(ParseError::UnknownCommand("x".to_string())));
+        assert_eq!(parse("!"), Err(ParseError::MisplacedBang));
+        assert_eq!(parse("7"), Err(ParseError::WrongNumberCount(1)));
+    }
+
+    #[test]
+    fn malformed_positions() {
+        assert_eq!(parse("3,,3"), Err(ParseError::MissingNumber));
+        assert_eq!(parse("3,"), Err(ParseError::MissingNumber));
+        assert_eq!(parse(",3"), Err(ParseError::UnknownCommand(",3".to_string())));
+        assert_eq!(parse("3,b"), Err(ParseError::InvalidNumber("b".to_string())));
+        assert_eq!(parse("reveal -1,2"), Err(ParseError::InvalidNumber("-1".to_string())));
+        assert_eq!(parse("1,99999999999999999999999"), Err(ParseError::InvalidNumber("99999999999999999999999".to_string())));
+        assert_eq!(parse("1,2,3,4"), Err(ParseError::WrongNumberCount(4)));
+    }
+
+    #[test]
+    fn missing_comma_between_numbers() {
+        assert_eq!(parse("3 3"), Err(ParseError::MissingComma("3".to_string(), "3".to_string())));
+        assert_eq!(parse("12 19!"), Err(ParseError::MissingComma("12".to_string(), "19!".to_string())));
+        assert_eq!(parse("reveal 2,2 4 5"), Err(ParseError::MissingComma("4".to_string(), "5".to_string())));
+        assert_eq!(parse_signed("-2 0"), Err(ParseError::MissingComma("-2".to_string(), "0".to_string())));
+        // une plage écrite avec des espaces reste une plage, et un nombre seul n'a qu'un nombre.
+        assert_eq!(parse("1 - 2 , 3").map(|commands| commands.len()), Ok(2));
+        assert_eq!(parse("3 3,4"), Err(ParseError::WrongNumberCount(1)));
+        assert_eq!(ParseError::MissingComma("3".to_string(), "3".to_string()).to_string(),
+            "A comma is missing in '3 3' : positions are written '3,3'...");
+    }
+
+    #[test]
+    fn misplaced_exclamation_marks() {
+        assert_eq!(parse("!3,3"), Err(ParseError::MisplacedBang));
+        assert_eq!(parse("3!,3"), Err(ParseError::MisplacedBang));
+        assert_eq!(parse("3,3!!"), Err(ParseError::MisplacedBang));
+        assert_eq!(parse("flag 3,3!"), Err(ParseError::MisplacedBang));
+    }
+
+    #[test]
+    fn several_commands_on_one_line() {
+        assert_eq!(
+            parse("3,4 5,6 7,7!"),
+            Ok(vec![Command::Reveal(at(3, 4)), Command::Reveal(at(5, 6)), Command::Flag(at(7, 7))])
+        );
+        assert_eq!(parse("hint 3,3"), Ok(vec![Command::Hint, Command::Reveal(at(3, 3))]));
+        assert_eq!(parse("flag 1,1 chord 2 , 2"), Ok(vec![Command::Flag(at(1, 1)), Command::Chord(at(2, 2))]));
+        // une seule erreur rejette toute la ligne.
+        assert_eq!(parse("3,4 5,x 7,7!"), Err(ParseError::InvalidNumber("x".to_string())));
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(parse("2-4,5!"), Ok(vec![Command::Flag(at(2, 5)), Command::Flag(at(3, 5)), Command::Flag(at(4, 5))]));
+        assert_eq!(parse("1 - 2 , 3-2"), Ok(vec![
+            Command::Reveal(at(1, 2)), Command::Reveal(at(1, 3)), Command::Reveal(at(2, 2)), Command::Reveal(at(2, 3)),
+        ]));
+        assert_eq!(parse("0-1,0,0").map(|commands| commands.len()), Ok(2));
+        assert_eq!(parse("2-,5"), Err(ParseError::InvalidNumber("2-".to_string())));
+        assert_eq!(parse("2-4-6,5"), Err(ParseError::InvalidNumber("2-4-6".to_string())));
+        assert_eq!(parse("0-99,0-99"), Err(ParseError::AreaTooLarge(10_000)));
+        assert_eq!(parse("0-18446744073709551615,0-1"), Err(ParseError::AreaTooLarge(usize::MAX)));
+        assert_eq!(parse("goto 1-2,3"), Err(ParseError::SingleSquareOnly("goto".to_string())));
+    }
+
+    #[test]
+    fn spreadsheet_notation() {
+        assert_eq!(parse("C12"), one(Command::Reveal(at(12, 2))));
+        assert_eq!(parse("aa5!"), one(Command::Flag(at(5, 26))));
+        assert_eq!(parse("flag Z0 goto AB3"), Ok(vec![Command::Flag(at(0, 25)), Command::Goto(at(3, 27))]));
+        assert_eq!(parse("C12 3,4"), Ok(vec![Command::Reveal(at(12, 2)), Command::Reveal(at(3, 4))]));
+        assert_eq!(parse("B2 - C3!"), Ok(vec![
+            Command::Flag(at(2, 1)), Command::Flag(at(2, 2)), Command::Flag(at(3, 1)), Command::Flag(at(3, 2)),
+        ]));
+        assert_eq!(parse("C3-A3").map(|commands| commands.len()), Ok(3));
+        assert_eq!(parse("c1x"), Err(ParseError::InvalidSquare("C1X".to_string())));
+        assert_eq!(parse("1c"), Err(ParseError::InvalidNumber("1c".to_string())));
+        assert_eq!(parse("c1,2"), Err(ParseError::InvalidSquare("C1,2".to_string())));
+        assert_eq!(parse("a1-a999"), Err(ParseError::AreaTooLarge(999)));
+        assert_eq!(parse("zzzzzzzzzzzzzzzz1"), Err(ParseError::InvalidSquare("ZZZZZZZZZZZZZZZZ1".to_string())));
+    }
+
+    #[test]
+    fn signed_positions_for_the_endless_mode() {
+        let at = |row: i64, col: i64| Position { layer: None, row, col };
+        assert_eq!(parse_signed("-2,0"), Ok(vec![Command::Reveal(at(-2, 0))]));
+        assert_eq!(parse_signed("12, -19 -5,15!"), Ok(vec![Command::Reveal(at(12, -19)), Command::Flag(at(-5, 15))]));
+        assert_eq!(parse_signed("goto -3,-4 d"), Ok(vec![Command::Goto(at(-3, -4)), Command::Scroll(0, 1)]));
+        assert_eq!(parse_signed("2-4,5"), Err(ParseError::InvalidNumber("2-4".to_string())));
+        assert_eq!(parse_signed("1,2,3"), Err(ParseError::WrongNumberCount(3)));
+        assert_eq!(parse_signed("1,99999999999999999999"), Err(ParseError::InvalidNumber("99999999999999999999".to_string())));
+        assert_eq!(parse_signed("-"), Err(ParseError::UnknownCommand("-".to_string())));
+    }
+
+    #[test]
+    fn column_labels() {
+        let labels: Vec<String> = [0, 1, 25, 26, 27, 51, 52, 701, 702].iter().map(|&col| Notation::Spreadsheet.column_label(col)).collect();
+        assert_eq!(labels, ["A", "B", "Z", "AA", "AB", "AZ", "BA", "ZZ", "AAA"]);
+        assert_eq!(Notation::Numeric.column_label(27), "27");
+        assert_eq!(Notation::Spreadsheet.square(12, 2), "C12");
+    }
+}
@@ -10,11 +10,49 @@
 /*
  *  Import des bibliothèques externes
  */
+use std::fmt::Display;
 use std::io;
+use std::time::Duration;
 use termion::{color, style};
 
-use crate::game::{Cell, Minesweeper};
-use super::read;
+use crate::game::{Cell, Config, GameMode, Minesweeper};
+use super::{grid, read};
+use super::command::Notation;
+use super::endless::Endless;
+use super::neighbourhood::Topology;
+
+/*
+ *  Définition des constantes d'affichage
+ */
+// taille minimale (en cases) de la fenêtre d'affichage : les petits plateaux restent affichés en entier.
+const MIN_VIEWPORT_SIZE: usize = 32;
+// lignes du terminal réservées au titre, aux numéros de colonnes, aux messages et à la saisie.
+const RESERVED_LINES: usize = 14;
+// colonnes du terminal réservées aux numéros de lignes et à la mini-carte.
+const RESERVED_COLUMNS: usize = 48;
+// dimensions maximales de la mini-carte (en caractères).
+const MINIMAP_MAX_COLS: usize = 32;
+const MINIMAP_MAX_ROWS: usize = 16;
+// ligne de la fenêtre à partir de laquelle la mini-carte est affichée.
+const MINIMAP_FIRST_LINE: usize = 4;
+// marque des bords d'un plateau torique.
+const WRAP_MARKER: char = '~';
+// espace entre deux couches d'un plateau en 3D.
+const LAYER_GAP: &str = "    ";
+// nombre de lignes entre la ligne du compte à rebours et la ligne de saisie du joueur (juste en dessous).
+const COUNTDOWN_LINES_UP: u16 = 1;
+
+/*
+ *  Définition des Structures
+ */
+// Partie du plateau affichée à l'écran.
+#[derive(Clone, Copy)]
+struct Viewport {
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize,
+}
 
 /*
  *  Fonction pour afficher un message d'erreur au joueur
@@ -29,6 +67,25 @@ pub fn print_message_and_clear(minesweeper_info: &mut Minesweeper) {
     }
 }
 
+/*
+ *  Fonction qui réécrit le temps restant sur sa ligne, au-dessus de la saisie du joueur (sans la déplacer)
+ */
+pub(crate) fn print_countdown(left: Duration, banner: &str) {
+    let seconds = left.as_secs_f64().ceil() as u64;
+    // le temps s'affiche en rouge pendant les dix dernières secondes.
+    let urgent = if seconds <= 10 { color::Fg(color::Red).to_string() } else { String::new() };
+    if let Err(err) = crossterm::execute!(
+            io::stdout(),
+            crossterm::cursor::SavePosition,
+            crossterm::cursor::MoveToPreviousLine(COUNTDOWN_LINES_UP),
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine),
+            crossterm::style::Print(format!("   {}{}Time left {:02}:{:02} \u{23F1}\u{FE0F}{}", banner, urgent, seconds / 60, seconds % 60, style::Reset)),
+            crossterm::cursor::RestorePosition
+        ) {
+        eprintln!("Error while showing the remaining time : {}", err);
+    }
+}
+
 /*
  *  Fonction pour effacer l'écran
  */
@@ -45,99 +102,476 @@ pub fn clean_screen() {
     }
 }
 
+/*
+ *  Fonction qui renvoie l'affichage (sur deux colonnes) d'une case, selon ce que le joueur peut en voir
+ */
+pub(crate) fn format_cell_view(cell: Cell, visible: bool, game_over: bool) -> String {
+    if cell == Cell::Hole {
+        return "  ".to_string(); // les trous du plateau restent vides
+    }
+    if !game_over && !visible {
+        return ". ".to_string(); // on cache les mines non révélées
+    }
+    match cell {
+        Cell::Undiscovered => ". ".to_string(),
+        Cell::Number(num) => {
+            // on applique des couleurs aux chiffres
+            let colored_number = match num {
+                // un zéro n'est affiché que par les variantes qui déforment les chiffres
+                0 => format!("{}{}0{}", color::Fg(color::LightBlack),  style::Bold, style::Reset),
+                1 => format!("{}{}1{}", color::Fg(color::Green),       style::Bold, style::Reset),
+                2 => format!("{}{}2{}", color::Fg(color::Yellow),      style::Bold, style::Reset),
+                3 => format!("{}{}3{}", color::Fg(color::LightYellow), style::Bold, style::Reset),
+                4 => format!("{}{}4{}", color::Fg(color::LightRed),    style::Bold, style::Reset),
+                5 => format!("{}{}5{}", color::Fg(color::Red),         style::Bold, style::Reset),
+                6 => format!("{}{}6{}", color::Fg(color::Magenta),     style::Bold, style::Reset),
+                7 => format!("{}{}7{}", color::Fg(color::Cyan),        style::Bold, style::Reset),
+                8 => format!("{}{}8{}", color::Fg(color::LightBlue),   style::Bold, style::Reset),
+                // au-delà de 8 (plusieurs mines par case ou grand voisinage)
+                _ => format!("{}{}{}{}", color::Fg(color::LightMagenta), style::Bold, num, style::Reset),
+            };
+            // un nombre à deux chiffres occupe déjà les deux colonnes de la case
+            if num >= 10 { colored_number } else { format!("{} ", colored_number) }
+        }
+        Cell::Mine(mines) => {
+            if game_over && mines > 1 {
+                // on affiche le nombre de mines devant la bombe (à la place de l'espace de la case précédente)
+                format!("\x08{}\u{1F4A3}", mines)
+            } else if game_over {
+                "\x08\u{1F4A3} ".to_string()
+            } else {
+                ". ".to_string() // on cache les mines non révélées
+            }
+        }
+        Cell::Empty => "* ".to_string(),
+        // en fin de partie : drapeau posé à tort et mine touchée
+        Cell::WrongMark => "\u{274C}".to_string(),
+        Cell::Exploded => "\u{1F4A5}".to_string(),
+        // annotation du joueur, qui n'est pas un drapeau
+        Cell::Question => format!("{}{}?{} ", color::Fg(color::LightCyan), style::Bold, style::Reset),
+        // case révélée (donc sûre) dont le chiffre est à retenir
+        Cell::Faded => format!("{}\u{2591}{} ", color::Fg(color::LightBlack), style::Reset),
+        Cell::Mark(1) => "\u{1F6A9}".to_string(),
+        // plusieurs drapeaux : leur nombre suivi d'un petit drapeau
+        Cell::Mark(flags) => format!("{}{}{}\u{2691}{}", color::Fg(color::LightRed), style::Bold, flags, style::Reset),
+        Cell::Hole => "  ".to_string(),
+    }
+}
+
+/*
+ *  Fonction qui renvoie l'affichage (sur deux colonnes) d'une case du plateau
+ */
+fn format_cell(minesweeper_info: &Minesweeper, row: usize, col: usize) -> String {
+    let visible = minesweeper_info.grid.has(row, col, grid::REVEALED | grid::FLAGGED | grid::QUESTION);
+    // en mode de mémoire, un chiffre révélé depuis trop longtemps est effacé.
+    let cell = if minesweeper_info.is_faded(row, col) { Cell::Faded } else { minesweeper_info.cell(row, col) };
+    format_cell_view(cell, visible, minesweeper_info.game_over)
+}
+
+/*
+ *  Fonction qui calcule le nombre de lignes et de colonnes de cases affichables dans le terminal
+ */
+fn terminal_viewport_size() -> (usize, usize) {
+    // sans terminal (sortie redirigée), on se contente de la taille minimale.
+    let (terminal_cols, terminal_rows) = crossterm::terminal::size().unwrap_or((0, 0));
+    let rows = (terminal_rows as usize).saturating_sub(RESERVED_LINES).max(MIN_VIEWPORT_SIZE);
+    let cols = ((terminal_cols as usize).saturating_sub(RESERVED_COLUMNS) / 2).max(MIN_VIEWPORT_SIZE);
+    (rows, cols)
+}
+
+/*
+ *  Implémentation de la fenêtre d'affichage
+ */
+impl Viewport {
+    /*
+     *  Fonction qui calcule la fenêtre centrée sur le curseur, selon la taille du terminal
+     */
+    fn around(cursor: (usize, usize), size: usize) -> Self {
+        Viewport::within(cursor, size, terminal_viewport_size())
+    }
+
+    /*
+     *  Fonction qui calcule la fenêtre centrée sur le curseur, pour un nombre de lignes et de colonnes affichables
+     */
+    fn within(cursor: (usize, usize), size: usize, (rows, cols): (usize, usize)) -> Self {
+        let (rows, cols) = (rows.min(size), cols.min(size));
+
+        // on centre la fenêtre sur le curseur sans dépasser les bords du plateau.
+        let start = |position: usize, length: usize| position.saturating_sub(length / 2).min(size - length);
+        Viewport { row: start(cursor.0, rows), col: start(cursor.1, cols), rows, cols }
+    }
+
+    /*
+     *  Fonction qui indique si la fenêtre ne montre qu'une partie du plateau
+     */
+    fn is_partial(&self, size: usize) -> bool {
+        self.rows < size || self.cols < size
+    }
+}
+
+/*
+ *  Fonction pour l'affichage des numéros de colonnes (un chiffre par ligne, quelle que soit la largeur)
+ */
+fn print_column_labels(view: Viewport, label_width: usize, notation: Notation) {
+    // les colonnes sont numérotées, ou nommées par des lettres comme dans un tableur.
+    let labels: Vec<String> = (view.col..view.col + view.cols).map(|col| notation.column_label(col)).collect();
+    let height = labels.iter().map(String::len).max().unwrap_or(1);
+    print_labels(&labels, label_width, height);
+}
+
+fn print_labels(columns: &[impl Display], label_width: usize, digits: usize) {
+    let labels: Vec<String> = columns.iter()
+        .map(|col| format!("{:>width$}", col, width = digits))
+        .collect();
+    for digit in 0..digits {
+        print!("{:width$}", "", width = label_width + 1);
+        for label in &labels {
+            print!(" {}", &label[digit..digit + 1]);
+        }
+        println!();
+    }
+}
+
+/*
+ *  Fonction pour l'affichage du nombre de mines de chaque colonne visible, sous le plateau
+ */
+fn print_column_totals(minesweeper_info: &Minesweeper, view: Viewport, label_width: usize) {
+    let totals: Vec<i64> = (view.col..view.col + view.cols).map(|col| minesweeper_info.grid.col_total(col) as i64).collect();
+    let digits = totals.iter().map(|total| total.to_string().len()).max().unwrap_or(1);
+    print!("{}{}", color::Fg(color::LightWhite), style::Bold);
+    print_labels(&totals, label_width, digits);
+    print!("{}", style::Reset);
+}
+
+/*
+ *  Fonction qui renvoie le compteur des vies restantes du joueur
+ */
+fn lives_counter(minesweeper_info: &Minesweeper) -> String {
+    let lives = minesweeper_info.configuration.lives;
+    format!("Lives {}/{} \u{2764}\u{FE0F}", lives - minesweeper_info.lives_lost.min(lives), lives)
+}
+
+/*
+ *  Fonction qui construit la mini-carte des zones explorées
+ */
+fn minimap_lines(minesweeper_info: &Minesweeper, view: Viewport) -> Vec<String> {
+    let size = minesweeper_info.grid.size();
+    let block_cols = size.div_ceil(MINIMAP_MAX_COLS);
+    let block_rows = size.div_ceil(MINIMAP_MAX_ROWS);
+    let (map_cols, map_rows) = (size.div_ceil(block_cols), size.div_ceil(block_rows));
+
+    // on compte les cases révélées de chaque bloc.
+    let mut revealed = vec![0usize; map_rows * map_cols];
+    for row in 0..size {
+        for col in 0..size {
+            // les trous du plateau n'ont rien à explorer : on les compte comme révélés.
+            if minesweeper_info.grid.has(row, col, grid::REVEALED | grid::MASKED) {
+                revealed[(row / block_rows) * map_cols + col / block_cols] += 1;
+            }
+        }
+    }
+
+    (0..map_rows).map(|map_row| {
+        let mut line = String::new();
+        for map_col in 0..map_cols {
+            // on choisit un caractère selon la proportion de cases révélées du bloc.
+            let rows_in_block = block_rows.min(size - map_row * block_rows);
+            let cols_in_block = block_cols.min(size - map_col * block_cols);
+            let explored = revealed[map_row * map_cols + map_col] * 4 / (rows_in_block * cols_in_block);
+            let symbol = ['\u{00B7}', '\u{2591}', '\u{2592}', '\u{2593}', '\u{2588}'][explored];
+
+            // on met en évidence les blocs visibles dans la fenêtre.
+            let in_view = map_row * block_rows < view.row + view.rows && (map_row + 1) * block_rows > view.row
+                && map_col * block_cols < view.col + view.cols && (map_col + 1) * block_cols > view.col;
+            if in_view {
+                line.push_str(&format!("{}{}{}", style::Invert, symbol, style::Reset));
+            } else {
+                line.push(symbol);
+            }
+        }
+        line
+    }).collect()
+}
+
+/*
+ *  Fonction qui renvoie le symbole en texte brut d'une case visible par le joueur (sans couleur ni emoji)
+ */
+pub(crate) fn plain_cell(minesweeper_info: &Minesweeper, row: usize, col: usize) -> String {
+    let visible = minesweeper_info.grid.has(row, col, grid::REVEALED | grid::FLAGGED | grid::QUESTION);
+    let cell = if minesweeper_info.is_faded(row, col) { Cell::Faded } else { minesweeper_info.cell(row, col) };
+    match cell {
+        Cell::Hole => " ".to_string(),
+        _ if !minesweeper_info.game_over && !visible => ".".to_string(),
+        Cell::Undiscovered => ".".to_string(),
+        Cell::Number(num) => num.to_string(),
+        Cell::Empty => "*".to_string(),
+        Cell::Faded => "#".to_string(),
+        Cell::Question => "?".to_string(),
+        // les cases à plusieurs mines ou drapeaux sont suivies de leur nombre
+        Cell::Mark(1) => "F".to_string(),
+        Cell::Mark(flags) => format!("F{}", flags),
+        Cell::Mine(1) => "M".to_string(),
+        Cell::Mine(mines) => format!("M{}", mines),
+        Cell::WrongMark => "x".to_string(),
+        Cell::Exploded => "X".to_string(),
+    }
+}
+
+/*
+ *  Fonction pour l'affichage du plateau en texte brut, sans effacer l'écran (parties scriptées)
+ */
+pub(crate) fn print_plain_board(minesweeper_info: &Minesweeper) {
+    let size = minesweeper_info.grid.size();
+    let label_width = (minesweeper_info.grid.rows() - 1).to_string().len();
+    let labels: Vec<String> = (0..size).map(|col| minesweeper_info.notation.column_label(col)).collect();
+    print_labels(&labels, label_width, labels.iter().map(String::len).max().unwrap_or(1));
+    for row in 0..minesweeper_info.grid.rows() {
+        // en 3D, les couches sont affichées les unes sous les autres, séparées par une ligne vide
+        if row > 0 && row % size == 0 {
+            println!();
+        }
+        let line: String = (0..size).map(|col| format!("{:<2}", plain_cell(minesweeper_info, row, col))).collect();
+        println!("{:<width$}  {}", row % size, line.trim_end(), width = label_width);
+    }
+}
+
 /*
  *  Fonction pour l'affichage du Démineur
  */
 pub fn print_board(minesweeper_info: &mut Minesweeper) {
+    // un plateau en 3D est affiché couche par couche
+    if minesweeper_info.grid.layers() > 1 {
+        print_layers(minesweeper_info);
+        return;
+    }
+
     // on efface l'écran à chaque tour
     clean_screen();
 
     println!("   Minesweeper game \u{1F579}\u{FE0F}\n");
 
+    // on calcule la partie du plateau visible autour du curseur
+    let size = minesweeper_info.grid.size();
+    let view = Viewport::around(minesweeper_info.cursor, size);
+    let partial = view.is_partial(size);
+    let label_width = (size - 1).to_string().len();
+
     // on affiche les numéros des colonnes
-    let mut row_count = 0;
-    let mut tens_count = 0;
-    print!("   ");
-    for _col in &minesweeper_info.board {
-        if tens_count == 0 {
-            print!("  ");
-        } else {
-            print!(" {}", tens_count);
+    print_column_labels(view, label_width, minesweeper_info.notation);
+
+    // sur un grand plateau, on affiche une mini-carte à droite de la fenêtre
+    let minimap = if partial { minimap_lines(minesweeper_info, view) } else { Vec::new() };
+
+    // sur une grille hexagonale, les lignes impaires sont décalées d'une demi-case vers la droite
+    let hex = minesweeper_info.grid.topology() == Topology::Hex;
+
+    // sur un tore, on marque d'un '~' les bords visibles qui rejoignent le bord opposé
+    let wraps = minesweeper_info.grid.wraps();
+    let edge = |at_edge: bool| if wraps && at_edge { WRAP_MARKER } else { ' ' };
+    // variante où le nombre de mines de chaque ligne et de chaque colonne est donné dans les marges
+    let line_totals = minesweeper_info.configuration.line_totals;
+
+    let edge_line = || println!("{:width$}  {}", "", format!("{} ", WRAP_MARKER).repeat(view.cols), width = label_width);
+    if wraps && view.row == 0 {
+        edge_line();
+    }
+
+    // on affiche le plateau du jeu
+    for (line, row) in (view.row..view.row + view.rows).enumerate() {
+        print!("{:<width$} {}", row, edge(view.col == 0), width = label_width);
+        if hex {
+            print!("{}", if row % 2 == 1 { " " } else { "" });
         }
-        if row_count == 9 {
-            tens_count += 1;
-            row_count = 0;
-        } else {
-            row_count += 1;
+        for col in view.col..view.col + view.cols {
+            let cell = format_cell(minesweeper_info, row, col);
+            if partial && (row, col) == minesweeper_info.cursor {
+                // on met en évidence le curseur
+                print!("{}{}{}", style::Invert, cell, style::Reset);
+            } else {
+                print!("{}", cell);
+            }
+        }
+        if hex && row % 2 != 1 {
+            // on réaligne la fin des lignes non décalées
+            print!(" ");
+        }
+        if wraps && view.col + view.cols == size {
+            print!("{}", WRAP_MARKER);
+        }
+        if line_totals {
+            // on affiche le nombre de mines de la ligne dans la marge
+            print!(" {}{}{:>2}{}", color::Fg(color::LightWhite), style::Bold, minesweeper_info.grid.row_total(row), style::Reset);
+        }
+        if line == 2 {
+            // on affiche le nombre de drapeau que le joueur peut encore poser
+            print!("    Flag {}/{} \u{1F6A9}",minesweeper_info.num_mark, minesweeper_info.configuration.num_mines)
+        }
+        if line == 3 && minesweeper_info.configuration.lives > 1 {
+            // on affiche le nombre de vies qu'il reste au joueur
+            print!("    {}", lives_counter(minesweeper_info))
+        }
+        if let Some(map_line) = line.checked_sub(MINIMAP_FIRST_LINE).and_then(|index| minimap.get(index)) {
+            print!("    {}", map_line);
         }
+        println!();
+    }
+    if wraps && view.row + view.rows == size {
+        edge_line();
+    }
+    if line_totals {
+        // on affiche le nombre de mines de chaque colonne sous le plateau
+        print_column_totals(minesweeper_info, view, label_width);
     }
-    row_count = 0;
-    print!("\n   ");
-    for _col in &minesweeper_info.board {
-        print!(" {}", row_count);
-        if row_count == 9 {
-            row_count = 0;
+
+    if partial {
+        // on indique la partie du plateau affichée
+        println!("\n   Rows {}-{} and columns {}-{} of {}x{} (cursor at {},{})",
+            view.row, view.row + view.rows - 1, view.col, view.col + view.cols - 1,
+            size, size, minesweeper_info.cursor.0, minesweeper_info.cursor.1);
+    }
+    // on affiche le message d'erreur s'il y en a un
+    print_message_and_clear(minesweeper_info);
+}
+
+/*
+ *  Fonction pour l'affichage d'un plateau en 3D : la couche du curseur, entre ses deux couches voisines
+ */
+fn print_layers(minesweeper_info: &mut Minesweeper) {
+    // on efface l'écran à chaque tour
+    clean_screen();
+
+    println!("   Minesweeper game \u{1F579}\u{FE0F}\n");
+
+    let size = minesweeper_info.grid.size();
+    let current = minesweeper_info.cursor.0 / size;
+    let panels: Vec<usize> = (current.saturating_sub(1)..=(current + 1).min(minesweeper_info.grid.layers() - 1)).collect();
+    let label_width = (size - 1).to_string().len();
+
+    // on calcule la partie visible de chaque couche autour du curseur : les couches se partagent la largeur du terminal
+    let (rows, cols) = terminal_viewport_size();
+    let view = Viewport::within((minesweeper_info.cursor.0 % size, minesweeper_info.cursor.1), size, (rows, cols / panels.len()));
+    let panel_width = label_width + 2 + 2 * view.cols;
+
+    // on affiche le nom des couches, en mettant en évidence celle du curseur
+    for &layer in &panels {
+        let title = format!("Layer {}", layer);
+        if layer == current {
+            print!("{}{:^width$}{}{}", style::Invert, title, style::Reset, LAYER_GAP, width = panel_width);
         } else {
-            row_count += 1;
+            print!("{:^width$}{}", title, LAYER_GAP, width = panel_width);
         }
     }
-    print!("\n");
+    println!("\n");
 
-    // on affiche le plateau du jeu
-    row_count = 0;
-    for row in &minesweeper_info.board {
-        print!("{}  ", row_count);
-        if row_count < 10 {
-            print!(" ");
+    // on affiche les numéros (ou les lettres) des colonnes de chaque couche
+    let notation = minesweeper_info.notation;
+    let height = notation.column_label(size - 1).len();
+    for digit in 0..height {
+        for _ in &panels {
+            print!("{:width$}", "", width = label_width + 2);
+            for col in view.col..view.col + view.cols {
+                print!("{} ", &format!("{:>width$}", notation.column_label(col), width = height)[digit..digit + 1]);
+            }
+            print!("{}", LAYER_GAP);
         }
-        for col in 0..minesweeper_info.configuration.board_size {
-            let cell = &row[col];
-            if minesweeper_info.game_over || minesweeper_info.revealed.contains(&(row_count, col)) {
-                match cell {
-                    Cell::Undiscovered => print!(". "),
-                    Cell::Number(num) => {
-                        // on applique des couleurs aux chiffres
-                        let colored_number = match num {
-                            1 => format!("{}{}1{}", color::Fg(color::Green),       style::Bold, style::Reset),
-                            2 => format!("{}{}2{}", color::Fg(color::Yellow),      style::Bold, style::Reset),
-                            3 => format!("{}{}3{}", color::Fg(color::LightYellow), style::Bold, style::Reset),
-                            4 => format!("{}{}4{}", color::Fg(color::LightRed),    style::Bold, style::Reset),
-                            5 => format!("{}{}5{}", color::Fg(color::Red),         style::Bold, style::Reset),
-                            6 => format!("{}{}6{}", color::Fg(color::Magenta),     style::Bold, style::Reset),
-                            _ => num.to_string(), // chiffres non colorés
-                        };
-                        print!("{} ", colored_number);
-                    }
-                    Cell::Mine => {
-                        if minesweeper_info.game_over {
-                            print!("\x08\u{1F4A3} ");
-                        } else {
-                            print!(". "); // on cache les mines non révélées
-                        }
-                    }
-                    Cell::Empty => print!("* "),
-                    Cell::Mark => print!("\u{1F6A9}"),
+        println!();
+    }
+
+    // on affiche les couches côte à côte : les couches voisines sont atténuées, mais leurs chiffres restent lisibles
+    for (line, row) in (view.row..view.row + view.rows).enumerate() {
+        for &layer in &panels {
+            print!("{:<width$}  ", row, width = label_width);
+            for col in view.col..view.col + view.cols {
+                let position = (layer * size + row, col);
+                let cell = format_cell(minesweeper_info, position.0, position.1);
+                if position == minesweeper_info.cursor {
+                    // on met en évidence le curseur
+                    print!("{}{}{}", style::Invert, cell, style::Reset);
+                } else if layer != current {
+                    print!("{}{}{}", style::Faint, cell, style::Reset);
+                } else {
+                    print!("{}", cell);
                 }
-            } else {
-                print!(". "); // on cache les mines non révélées
             }
+            print!("{}", LAYER_GAP);
         }
-        if row_count == 2 {
+        if line == 2 {
             // on affiche le nombre de drapeau que le joueur peut encore poser
-            print!("    Flag {}/{} \u{1F6A9}",minesweeper_info.num_mark, minesweeper_info.configuration.num_mines)
+            print!("Flag {}/{} \u{1F6A9}", minesweeper_info.num_mark, minesweeper_info.configuration.num_mines)
+        }
+        if line == 3 && minesweeper_info.configuration.lives > 1 {
+            // on affiche le nombre de vies qu'il reste au joueur
+            print!("{}", lives_counter(minesweeper_info))
         }
         println!();
-        // on incrémente le nombre de ligne
-        row_count += 1;
     }
+
+    if view.is_partial(size) {
+        // on indique la partie de chaque couche affichée
+        println!("\n   Rows {}-{} and columns {}-{} of each {}x{} layer",
+            view.row, view.row + view.rows - 1, view.col, view.col + view.cols - 1, size, size);
+    }
+    // on indique la couche affichée et comment en changer
+    println!("\n   Layer {} of 0-{} (cursor at {},{},{}) : 'q' and 'e' show the previous and next layer",
+        current, minesweeper_info.grid.layers() - 1, current, minesweeper_info.cursor.0 % size, minesweeper_info.cursor.1);
     // on affiche le message d'erreur s'il y en a un
     print_message_and_clear(minesweeper_info);
 }
 
+/*
+ *  Fonction pour l'affichage du champ de mines infini, autour du curseur
+ */
+pub(crate) fn print_endless(world: &mut Endless) {
+    // on efface l'écran à chaque tour
+    clean_screen();
+
+    println!("   Endless minesweeper \u{1F579}\u{FE0F}\n");
+
+    // on calcule la partie du champ visible autour du curseur
+    let (rows, cols) = terminal_viewport_size();
+    let first_row = world.cursor.0 - rows as i64 / 2;
+    let first_col = world.cursor.1 - cols as i64 / 2;
+    let row_range = first_row..first_row + rows as i64;
+    let columns: Vec<i64> = (first_col..first_col + cols as i64).collect();
+
+    // on affiche les numéros des colonnes (qui peuvent être négatifs)
+    let label_width = [row_range.start, row_range.end - 1, columns[0], columns[columns.len() - 1]]
+        .iter().map(|value| value.to_string().len()).max().unwrap_or(1);
+    print_labels(&columns, label_width, label_width);
+
+    // on affiche le champ de mines
+    for (line, row) in row_range.enumerate() {
+        print!("{:<width$}  ", row, width = label_width);
+        for &col in &columns {
+            let visible = world.has(row, col, grid::REVEALED | grid::FLAGGED);
+            let cell = format_cell_view(world.cell(row, col), visible, world.game_over);
+            if (row, col) == world.cursor {
+                // on met en évidence le curseur
+                print!("{}{}{}", style::Invert, cell, style::Reset);
+            } else {
+                print!("{}", cell);
+            }
+        }
+        match line {
+            // on affiche le score et le nombre de drapeaux posés
+            2 => print!("    Score {} \u{2B50}", world.score),
+            3 => print!("    Flag {} \u{1F6A9}", world.num_mark),
+            _ => {}
+        }
+        println!();
+    }
+
+    println!("\n   Cursor at {},{}", world.cursor.0, world.cursor.1);
+    // on affiche le message s'il y en a un
+    if let Some(message) = world.player_message.take() {
+        println!("\n{}\n", message);
+    }
+}
+
 /*
  *  Fonction pour l'affichage du Menu
  */
-pub fn print_menu() -> u32 {
+pub fn print_menu(config: &Config) -> u32 {
     // on déclare une variable mutable pour gérer la saisie du joueur
     let mut input = String::new();
 
@@ -150,11 +584,13 @@ pub fn print_menu() -> u32 {
         println!("      \x08\u{1F4A3} Welcome to the Minesweeper game ! \u{1F6A9}\n");
         println!("Please press any key given below to continue :\n");
         println!(" 1 : Minesweeper game rule and How to play. \u{1F4DC}");
-        println!(" 2 : Small 8x8 grid with 10 mines.");
-        println!(" 3 : Medium 16x16 grid with 40 mines.");
-        println!(" 4 : Large 32x32 grid with 160 mines.");
-        println!(" 5 : Xtreme 16x16 grid with 246 mines. \u{2620}\u{FE0F}\n");
-        println!(" Soon : configure your own grid...\n");
+        // on affiche les modes de jeu du fichier de configuration, triés par index
+        let mut game_modes: Vec<&GameMode> = config.game_modes.iter().collect();
+        game_modes.sort_by_key(|mode| mode.index);
+        for mode in game_modes {
+            println!(" {} : {}", mode.index, mode.description());
+        }
+        println!("\n Soon : configure your own grid...\n");
         println!(" 9 : Quit game. \u{1F6AA}\n");
 
         // on regarde si la variable contenant le message d'erreur est vide
@@ -167,13 +603,15 @@ pub fn print_menu() -> u32 {
 
         println!("Enter your choice (e.g., '1', '4'):");
 
-        // on récupère la saisie du joueur
-        read::read_user_input(&mut input);
+        // on récupère la saisie du joueur (à la fin de l'entrée, on quitte le jeu)
+        if !read::read_user_input(&mut input) {
+            return 9;
+        }
 
         // on analyse l'entrée de l'utilisateur        
         match input.trim().parse() {
-            // si le chiffre est compris entre 1 et 5 
-            Ok(nombre) if nombre >= 1 && nombre <= 5 => {
+            // si le chiffre correspond aux règles ou à un mode de jeu
+            Ok(nombre) if nombre == 1 || config.game_modes.iter().any(|mode| mode.index == nombre) => {
                 // on renvoie le chiffre
                 return nombre; 
             }
@@ -217,7 +655,36 @@ pub fn print_game_rule() -> u32 {
         println!("  If you want to uncover a tile where a mine is, then you lose.\n");
         println!("  To win, you will need to let uncovered the tile where you think the mines are. ");
         println!("  To ease the things for you, you can mark the mines with a flag where you think the mines are.");
-        println!("  (Enter a row then col with '!' for a flag (e.g., '3,3!' , '5,15!')).\n");
+        println!("  (Enter a row then col with '!' for a flag (e.g., '3,3!' , '5,15!')).");
+        println!("  You can enter several moves at once (e.g., '3,4 5,6 7,7!') and ranges of rows or columns (e.g., '2-4,5!').");
+        println!("  Squares can also be written like in a spreadsheet, column letters then row (e.g., 'C12' for '12,2', 'B2-D4!') :");
+        println!("  start the game with '--letters' to name the columns with letters on the board.");
+        println!("  If you are stuck, enter 'hint' for a safe square, 'undo' to take back your last move, or 'auto' and the bot");
+        println!("  will finish the game for you (undo is not available with lives or fading numbers).");
+        println!("  Enter 'save' to resume the game later and 'help' for all commands.");
+        println!("  To give up, enter 'restart' (new board), 'retry' (same board), 'menu' or 'quit' : a started game counts as abandoned.");
+        println!("  On big boards, only a part of the grid is shown around the cursor (the last square played) :");
+        println!("  enter 'w', 'a', 's' or 'd' to scroll, or 'goto row,col' to jump (e.g., 'goto 500,250').");
+        println!("  On hexagonal grids, odd rows are shifted by half a square and each square has 6 neighbours :");
+        println!("  2 on the row above, 2 on its own row and 2 on the row below.");
+        println!("  On wrap-around grids, the edges marked with '~' touch the opposite edge.");
+        println!("  Some modes use other neighbourhoods (e.g., knight moves) : the menu gives the number of neighbours.");
+        println!("  3D grids are stacks of layers where each square has 26 neighbours, on its own layer and the two next to it :");
+        println!("  enter 'layer,row,col' (e.g., '1,2,0', '0,3,3!'), or just 'row,col' on the shown layer, and 'q' or 'e' to switch layers.");
+        println!("  Shaped grids have holes, shown blank : they never hold a mine and are not neighbours of any square.");
+        println!("  When a square can hold several mines, numbers give the total count around and flags stack :");
+        println!("  mark the same square again to add a flag, up to the maximum, then once more to remove them all.");
+        println!("  Some modes distort the numbers : 'liar' numbers are off by exactly one (too high or too low),");
+        println!("  'parity' numbers only show 0 for an even count and 1 for an odd one, and other modes count a differently-shaped area.");
+        println!("  In modes with several lives, hitting a mine costs a life and flags it, and the game goes on until no life is left.");
+        println!("  Timed modes show the time left above the prompt : a countdown is lost when the time runs out,");
+        println!("  and a time attack chains boards until the time runs out, each cleared board adding its 3BV to the score.");
+        println!("  When you lose, the board shows the mine you hit (\u{1F4A5}), your flags on mines (\u{1F6A9}) and your wrong flags (\u{274C}).");
+        println!("  Some modes add a '?' after the flag when marking a square again : it is only a reminder, not a flag.");
+        println!("  Entering the position of a revealed number reveals all its neighbours when it has as many flags around (chording).");
+        println!("  In memory modes, revealed squares fade to '\u{2591}' after a while : remember their numbers !");
+        println!("  Some modes give the number of mines of each row (on its right) and of each column (below the board).");
+        println!("  Modes marked 'No guess' can always be solved without guessing.\n");
         println!("  There is an example below :\n");
         println!("      . . .           * * * ");
         println!("      . {}{}1{} .           * {}{}1{} * ",color::Fg(color::LightGreen), style::Bold, style::Reset, color::Fg(color::LightGreen), style::Bold, style::Reset);
@@ -240,8 +707,10 @@ pub fn print_game_rule() -> u32 {
 
         println!("You are ready, press 1 to get out of this menu.");
 
-        // on récupère la saisie du joueur
-        read::read_user_input(&mut input);
+        // on récupère la saisie du joueur (à la fin de l'entrée, on sort du menu)
+        if !read::read_user_input(&mut input) {
+            return 1;
+        }
 
         // on analyse l'entrée de l'utilisateur
         match input.trim().parse() {
@@ -260,4 +729,36 @@ pub fn print_game_rule() -> u32 {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/*
+ *  Tests de la fenêtre d'affichage
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corner(view: Viewport) -> (usize, usize, usize, usize) {
+        (view.row, view.col, view.rows, view.cols)
+    }
+
+    #[test]
+    fn viewport_is_centred_on_the_cursor() {
+        assert_eq!(corner(Viewport::within((500, 300), 1000, (40, 60))), (480, 270, 40, 60));
+    }
+
+    #[test]
+    fn viewport_is_clamped_at_the_board_edges() {
+        // coin en haut à gauche, puis en bas à droite : la fenêtre ne déborde pas du plateau.
+        assert_eq!(corner(Viewport::within((0, 3), 1000, (40, 60))), (0, 0, 40, 60));
+        assert_eq!(corner(Viewport::within((999, 990), 1000, (40, 60))), (960, 940, 40, 60));
+    }
+
+    #[test]
+    fn small_boards_are_shown_whole() {
+        let view = Viewport::within((7, 7), 8, (40, 60));
+        assert_eq!(corner(view), (0, 0, 8, 8));
+        assert!(!view.is_partial(8));
+        assert!(Viewport::within((7, 7), 80, (40, 60)).is_partial(80));
+    }
+}
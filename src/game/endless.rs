@@ -0,0 +1,461 @@
+/*
+ *      Nom du fichier : endless.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::{Rng, SeedableRng};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::game::{self, Cell, NextStep};
+use super::command::{self, Command, Position};
+use super::{display, grid, read};
+
+/*
+ *  Définition des constantes
+ */
+// fichier de sauvegarde du monde infini.
+pub(crate) const ENDLESS_SAVE_PATH: &str = "endless_save.json";
+// nombre maximal de cases révélées à chaque tour par une propagation (le champ n'a pas de bord) : la suite attend le tour suivant.
+const MAX_CASCADE: usize = 100_000;
+// nombre de cases parcourues par le curseur à chaque défilement ('w', 'a', 's', 'd').
+const SCROLL_STEP: i64 = 10;
+// coordonnée la plus éloignée du champ, dans chaque direction (pour que les calculs autour d'une case ne débordent jamais).
+pub(crate) const MAX_COORDINATE: i64 = i32::MAX as i64;
+
+/*
+ *  Définition des Structures
+ */
+// Morceau carré du monde, généré à la demande à partir de la graine.
+struct Chunk {
+    flags: Vec<u8>,
+}
+
+// Champ de mines infini : les morceaux sont générés au fur et à mesure de l'exploration.
+pub(crate) struct Endless {
+    seed: u64,
+    chunk_size: usize,
+    mines_per_chunk: usize,
+    chunks: HashMap<(i64, i64), Chunk>,
+    // cases vides révélées dont les voisines restent à révéler (propagation interrompue par MAX_CASCADE).
+    frontier: VecDeque<(i64, i64)>,
+    pub(crate) score: usize,
+    pub(crate) num_mark: usize,
+    pub(crate) game_over: bool,
+    pub(crate) cursor: (i64, i64),
+    pub(crate) player_message: Option<String>,
+}
+
+// Monde sauvegardé : les mines se régénèrent à partir de la graine, on ne garde que l'état du joueur.
+#[derive(Serialize, Deserialize)]
+struct SavedWorld {
+    seed: u64,
+    chunk_size: usize,
+    mines_per_chunk: usize,
+    revealed: Vec<(i64, i64)>,
+    flagged: Vec<(i64, i64)>,
+    cursor: (i64, i64),
+    #[serde(default)]
+    frontier: Vec<(i64, i64)>,
+}
+
+/*
+ *  Fonction de mélange (splitmix64) qui donne une graine stable à chaque morceau
+ */
+fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/*
+ *  Implémentation du champ de mines infini
+ */
+impl Endless {
+    pub(crate) fn new(seed: u64, chunk_size: usize, mines_per_chunk: usize) -> Self {
+        Endless {
+            seed,
+            chunk_size: chunk_size.max(1),
+            mines_per_chunk,
+            chunks: HashMap::new(),
+            frontier: VecDeque::new(),
+            score: 0,
+            num_mark: 0,
+            game_over: false,
+            cursor: (0, 0),
+            player_message: Some("".to_string()),
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie le morceau contenant une case et la position de la case dans ce morceau
+     */
+    fn locate(&self, row: i64, col: i64) -> ((i64, i64), usize) {
+        let size = self.chunk_size as i64;
+        let key = (row.div_euclid(size), col.div_euclid(size));
+        (key, (row.rem_euclid(size) * size + col.rem_euclid(size)) as usize)
+    }
+
+    /*
+     *  Fonction qui génère les mines d'un morceau (toujours les mêmes pour une graine donnée)
+     */
+    fn generate_chunk(&self, (chunk_row, chunk_col): (i64, i64)) -> Chunk {
+        let size = self.chunk_size;
+        let chunk_seed = mix(self.seed ^ mix(chunk_row as u64 ^ mix(chunk_col as u64)));
+        let mut rng = StdRng::seed_from_u64(chunk_seed);
+        let mut flags = vec![0; size * size];
+
+        for cell in index::sample(&mut rng, size * size, self.mines_per_chunk.min(size * size)) {
+            // la case de départ (0,0) et ses voisines ne contiennent jamais de mine.
+            let row = chunk_row * size as i64 + (cell / size) as i64;
+            let col = chunk_col * size as i64 + (cell % size) as i64;
+            if row.abs() > 1 || col.abs() > 1 {
+                flags[cell] = grid::MINE;
+            }
+        }
+        Chunk { flags }
+    }
+
+    /*
+     *  Fonctions de lecture et d'écriture de l'état d'une case (le morceau est généré si besoin)
+     */
+    fn flags(&mut self, row: i64, col: i64) -> u8 {
+        let (key, index) = self.locate(row, col);
+        if !self.chunks.contains_key(&key) {
+            let chunk = self.generate_chunk(key);
+            self.chunks.insert(key, chunk);
+        }
+        self.chunks[&key].flags[index]
+    }
+
+    fn set(&mut self, row: i64, col: i64, flag: u8) {
+        let current = self.flags(row, col);
+        let (key, index) = self.locate(row, col);
+        if let Some(chunk) = self.chunks.get_mut(&key) {
+            chunk.flags[index] = current | flag;
+        }
+    }
+
+    fn clear(&mut self, row: i64, col: i64, flag: u8) {
+        let current = self.flags(row, col);
+        let (key, index) = self.locate(row, col);
+        if let Some(chunk) = self.chunks.get_mut(&key) {
+            chunk.flags[index] = current & !flag;
+        }
+    }
+
+    pub(crate) fn has(&mut self, row: i64, col: i64, flag: u8) -> bool {
+        self.flags(row, col) & flag != 0
+    }
+
+    /*
+     *  Fonction qui indique si une case est dans les limites (très lointaines) du champ
+     */
+    pub(crate) fn contains(row: i64, col: i64) -> bool {
+        row.abs() <= MAX_COORDINATE && col.abs() <= MAX_COORDINATE
+    }
+
+    /*
+     *  Fonction qui renvoie les 8 voisins d'une case (sauf au-delà des limites du champ)
+     */
+    fn neighbours(row: i64, col: i64) -> impl Iterator<Item = (i64, i64)> {
+        (-1..=1).flat_map(move |dr| (-1..=1).map(move |dc| (row.saturating_add(dr), col.saturating_add(dc))))
+            .filter(move |&(r, c)| (r, c) != (row, col) && Endless::contains(r, c))
+    }
+
+    /*
+     *  Fonction qui déplace le curseur, sans sortir des limites du champ
+     */
+    pub(crate) fn move_cursor(&mut self, delta_row: i64, delta_col: i64) {
+        self.cursor = (
+            self.cursor.0.saturating_add(delta_row).clamp(-MAX_COORDINATE, MAX_COORDINATE),
+            self.cursor.1.saturating_add(delta_col).clamp(-MAX_COORDINATE, MAX_COORDINATE),
+        );
+    }
+
+    /*
+     *  Fonction pour déterminer le nombre de mines autour d'une case, y compris dans les morceaux voisins
+     */
+    pub(crate) fn count_mines_around(&mut self, row: i64, col: i64) -> u8 {
+        Endless::neighbours(row, col).filter(|&(r, c)| self.has(r, c, grid::MINE)).count() as u8
+    }
+
+    /*
+     *  Fonction pour révéler les cellules (renvoie les cases nouvellement révélées)
+     */
+    pub(crate) fn reveal(&mut self, row: i64, col: i64) -> Vec<(i64, i64)> {
+        let mut newly_revealed = Vec::new();
+
+        // on ignore les cases déjà révélées ou marquées d'un drapeau.
+        if self.has(row, col, grid::REVEALED | grid::FLAGGED) {
+            return newly_revealed;
+        }
+
+        // si la case contient une mine, la partie est terminée.
+        if self.has(row, col, grid::MINE) {
+            self.game_over = true;
+            return newly_revealed;
+        }
+
+        self.set(row, col, grid::REVEALED);
+        self.score += 1;
+        newly_revealed.push((row, col));
+        self.frontier.push_back((row, col));
+        newly_revealed.extend(self.spread());
+        newly_revealed
+    }
+
+    /*
+     *  Fonction qui poursuit la propagation des cases vides, d'au plus MAX_CASCADE cases par appel
+     *  (les cases restantes sont gardées pour l'appel suivant, le champ n'ayant pas de bord)
+     */
+    pub(crate) fn spread(&mut self) -> Vec<(i64, i64)> {
+        let mut newly_revealed = Vec::new();
+        while newly_revealed.len() < MAX_CASCADE {
+            let Some((r, c)) = self.frontier.pop_front() else { break };
+            if self.count_mines_around(r, c) != 0 {
+                continue;
+            }
+            for (nr, nc) in Endless::neighbours(r, c) {
+                if !self.has(nr, nc, grid::REVEALED | grid::FLAGGED | grid::MINE) {
+                    self.set(nr, nc, grid::REVEALED);
+                    self.score += 1;
+                    newly_revealed.push((nr, nc));
+                    self.frontier.push_back((nr, nc));
+                }
+            }
+        }
+        // on prévient le joueur, sans cacher un autre message.
+        if !self.frontier.is_empty() && self.player_message.as_deref().is_none_or(str::is_empty) {
+            self.player_message = Some("This area is huge : it keeps opening up while you play...".to_string());
+        }
+        newly_revealed
+    }
+
+    /*
+     *  Fonction pour marquer (ou démarquer) une mine
+     */
+    pub(crate) fn mark_mine(&mut self, row: i64, col: i64) {
+        if self.has(row, col, grid::FLAGGED) {
+            self.clear(row, col, grid::FLAGGED);
+            self.num_mark -= 1;
+        } else if !self.has(row, col, grid::REVEALED) {
+            self.set(row, col, grid::FLAGGED);
+            self.num_mark += 1;
+        } else {
+            self.player_message = Some("This square has already been revealed...".to_string());
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie le contenu d'une case tel qu'il est affiché
+     */
+    pub(crate) fn cell(&mut self, row: i64, col: i64) -> Cell {
+        if self.has(row, col, grid::FLAGGED) {
+            Cell::Mark(1)
+        } else if self.has(row, col, grid::REVEALED) {
+            match self.count_mines_around(row, col) {
+                0 => Cell::Empty,
+                n => Cell::Number(n),
+            }
+        } else if self.has(row, col, grid::MINE) {
+            Cell::Mine(1)
+        } else {
+            Cell::Undiscovered
+        }
+    }
+
+    /*
+     *  Fonction qui sauvegarde le monde dans un fichier JSON
+     */
+    pub(crate) fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let size = self.chunk_size as i64;
+        let mut saved = SavedWorld {
+            seed: self.seed,
+            chunk_size: self.chunk_size,
+            mines_per_chunk: self.mines_per_chunk,
+            revealed: Vec::new(),
+            flagged: Vec::new(),
+            cursor: self.cursor,
+            frontier: self.frontier.iter().copied().collect(),
+        };
+
+        // on parcourt les morceaux générés pour relever les cases révélées et les drapeaux.
+        for (&(chunk_row, chunk_col), chunk) in &self.chunks {
+            for (index, &flags) in chunk.flags.iter().enumerate() {
+                let cell = (chunk_row * size + index as i64 / size, chunk_col * size + index as i64 % size);
+                if flags & grid::REVEALED != 0 {
+                    saved.revealed.push(cell);
+                }
+                if flags & grid::FLAGGED != 0 {
+                    saved.flagged.push(cell);
+                }
+            }
+        }
+        saved.revealed.sort_unstable();
+        saved.flagged.sort_unstable();
+
+        serde_json::to_writer(BufWriter::new(File::create(path)?), &saved)?;
+        Ok(())
+    }
+
+    /*
+     *  Fonction qui recharge un monde sauvegardé
+     */
+    pub(crate) fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let saved: SavedWorld = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+
+        // les mines sont régénérées à partir de la graine, on réapplique l'état du joueur.
+        let mut world = Endless::new(saved.seed, saved.chunk_size, saved.mines_per_chunk);
+        for &(row, col) in &saved.revealed {
+            world.set(row, col, grid::REVEALED);
+        }
+        for &(row, col) in &saved.flagged {
+            world.set(row, col, grid::FLAGGED);
+        }
+        world.score = saved.revealed.len();
+        world.num_mark = saved.flagged.len();
+        world.frontier = saved.frontier.into_iter().filter(|&(row, col)| Endless::contains(row, col)).collect();
+        // un fichier modifié à la main ne doit pas envoyer le curseur au-delà des limites du champ.
+        world.cursor = saved.cursor;
+        world.move_cursor(0, 0);
+        Ok(world)
+    }
+}
+
+/*
+ *  Fonction qui joue une action sur une case saisie par le joueur, si elle est dans les limites du champ
+ */
+fn play_at(world: &mut Endless, position: Position<i64>, action: fn(&mut Endless, i64, i64)) {
+    let (row, col) = (position.row, position.col);
+    if !Endless::contains(row, col) {
+        world.player_message = Some(format!("The field stops at {} squares from the start, in every direction...", MAX_COORDINATE));
+        return;
+    }
+    world.cursor = (row, col);
+    action(world, row, col);
+}
+
+/*
+ *  Fonction qui demande au joueur de confirmer qu'il quitte le monde (sa progression non sauvegardée est perdue)
+ */
+fn confirm_leaving(world: &mut Endless) -> bool {
+    game::confirm("Unsaved progress in this world will be lost : enter 'y' to leave it, or anything else to keep playing :", &mut world.player_message)
+}
+
+/*
+ *  Fonction qui exécute une commande du joueur (renvoie la suite choisie si le joueur quitte le monde)
+ */
+fn run_command(world: &mut Endless, command: Command<i64>) -> Option<NextStep> {
+    match command {
+        Command::Reveal(position) => play_at(world, position, |world, row, col| {
+            if world.has(row, col, grid::REVEALED) {
+                world.player_message = Some("This square has already been revealed...".to_string());
+            } else if world.has(row, col, grid::FLAGGED) {
+                world.player_message = Some("This square is flagged : remove the flag before revealing it...".to_string());
+            } else {
+                world.reveal(row, col);
+            }
+        }),
+        Command::Flag(position) => play_at(world, position, Endless::mark_mine),
+        Command::Goto(position) if Endless::contains(position.row, position.col) => world.cursor = (position.row, position.col),
+        Command::Goto(_) => {
+            world.player_message = Some(format!("The field stops at {} squares from the start, in every direction...", MAX_COORDINATE));
+        }
+        Command::Scroll(delta_row, delta_col) => world.move_cursor(delta_row as i64 * SCROLL_STEP, delta_col as i64 * SCROLL_STEP),
+        Command::Save => {
+            // on sauvegarde le monde pour le reprendre plus tard.
+            world.player_message = Some(match world.save(ENDLESS_SAVE_PATH) {
+                Ok(()) => format!("World saved in '{}'.", ENDLESS_SAVE_PATH),
+                Err(err) => format!("The world could not be saved : {}", err),
+            });
+        }
+        Command::Help => world.player_message = Some(command::HELP.to_string()),
+        // le joueur quitte le monde (qui peut avoir été sauvegardé) : pour le jeu, pour le menu, ou pour un nouveau monde.
+        // un monde infini n'a pas d'issue à enregistrer : il ne compte pas dans les résultats, même abandonné.
+        Command::Quit => return Some(NextStep::Quit).filter(|_| confirm_leaving(world)),
+        Command::Menu => return Some(NextStep::Menu).filter(|_| confirm_leaving(world)),
+        Command::Restart => return Some(NextStep::Restart).filter(|_| confirm_leaving(world)),
+        Command::Chord(_) | Command::Layer(_) | Command::Hint | Command::Undo | Command::Auto | Command::Retry => {
+            world.player_message = Some("This command is not available in the endless mode (type 'help' to list the commands)...".to_string());
+        }
+    }
+    None
+}
+
+/*
+ *  Fonction qui gère une partie en mode infini
+ */
+pub(crate) fn play(chunk_size: usize, mines_per_chunk: usize) -> NextStep {
+    let mut input = String::new();
+
+    // on propose de reprendre le monde sauvegardé s'il existe.
+    let mut world = Endless::new(rand::thread_rng().gen(), chunk_size, mines_per_chunk);
+    if Path::new(ENDLESS_SAVE_PATH).exists() {
+        println!("A saved endless world was found. Enter 'load' to continue it, or anything else for a new world :");
+        read::read_user_input(&mut input);
+        if input.trim() == "load" {
+            match Endless::load(ENDLESS_SAVE_PATH) {
+                Ok(saved) => world = saved,
+                Err(err) => world.player_message = Some(format!("The saved world could not be loaded : {}", err)),
+            }
+        }
+    }
+
+    // le champ commence toujours par la case (0,0), qui ne contient jamais de mine.
+    if world.score == 0 {
+        world.reveal(0, 0);
+    }
+
+    while !world.game_over {
+        display::print_endless(&mut world);
+        println!("Other commands : 'goto row,col' to jump, 'w' 'a' 's' 'd' to scroll, 'save' to save the world, 'help' for all commands.");
+        println!("Enter row and column (e.g., '-2,0' , '12,-19') or mark a mine (e.g., '3,3!' , '-5,15!') :");
+
+        input.clear();
+        // à la fin de l'entrée, on quitte le monde (qui peut avoir été sauvegardé) sans parler de mine.
+        if !read::read_user_input(&mut input) {
+            println!("\nInput closed : leaving the endless world after clearing {} safe squares.", world.score);
+            return NextStep::Quit;
+        }
+
+        // on joue les commandes de la ligne dans l'ordre, jusqu'à une mine.
+        match command::parse_signed(&input) {
+            Ok(commands) => {
+                for command in commands {
+                    if let Some(next) = run_command(&mut world, command) {
+                        return next;
+                    }
+                    if world.game_over {
+                        break;
+                    }
+                }
+            }
+            Err(err) => world.player_message = Some(err.to_string()),
+        }
+
+        // une propagation trop vaste pour un seul tour se poursuit à chaque tour.
+        if !world.game_over {
+            world.spread();
+        }
+    }
+
+    // la partie s'arrête à la première mine : le score est le nombre de cases sûres dégagées.
+    display::print_endless(&mut world);
+    println!("Game Over ! You hit a mine after clearing {} safe squares.\n", world.score);
+    NextStep::Ask
+}
@@ -0,0 +1,410 @@
+/*
+ *      Nom du fichier : grid.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use rand::Rng;
+
+use super::clues::ClueRule;
+use super::neighbourhood::{Neighbourhood, Topology};
+
+/*
+ *  Définition des drapeaux d'état d'une case (un octet par case)
+ */
+pub(crate) const MINE: u8 = 0b001;
+pub(crate) const REVEALED: u8 = 0b010;
+pub(crate) const FLAGGED: u8 = 0b100;
+// case retirée du plateau par un masque : jamais minée, jamais comptée, jamais révélée.
+pub(crate) const MASKED: u8 = 0b1000;
+// mine touchée : connue du joueur et couverte de drapeaux si la partie continue (plusieurs vies),
+// ou mine qui a fait perdre la partie.
+pub(crate) const EXPLODED: u8 = 0b1_0000;
+// case annotée d'un point d'interrogation : simple aide-mémoire, ni drapeau ni révélée.
+pub(crate) const QUESTION: u8 = 0b10_0000;
+// nombre maximal de mines (et de drapeaux) d'une case : chacun tient sur 4 bits.
+pub(crate) const MAX_STACK: u8 = 0x0F;
+
+/*
+ *  Définition des Structures
+ */
+// Plateau dense : les cases sont rangées ligne par ligne dans des tableaux plats.
+// un plateau en 3D empile ses couches : la ligne `r` de la couche `l` est la ligne `l * size + r`.
+#[derive(Clone)]
+pub(crate) struct Grid {
+    size: usize,
+    layers: usize,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    flags: Vec<u8>,
+    counts: Vec<u8>,
+    masked: usize,
+    mined: usize,
+    // variante à plusieurs mines par case : nombre de mines (4 bits de poids faible)
+    // et de drapeaux (4 bits de poids fort) de chaque case, vide s'il n'y a qu'une mine par case.
+    mines_per_cell: u8,
+    stacks: Vec<u8>,
+    // variantes de chiffres : règle d'affichage, voisinage compté (s'il diffère de celui des cases)
+    // et chiffres affichés, calculés une fois les mines posées (vide pour les chiffres exacts).
+    clue_rule: ClueRule,
+    clue_neighbourhood: Option<Neighbourhood>,
+    clues: Vec<u8>,
+}
+
+/*
+ *  Implémentation du plateau dense
+ */
+impl Grid {
+    pub(crate) fn new(size: usize, layers: usize, topology: Topology, neighbourhood: Neighbourhood) -> Self {
+        Grid {
+            size,
+            layers,
+            topology,
+            neighbourhood,
+            flags: vec![0; size * size * layers],
+            counts: vec![0; size * size * layers],
+            masked: 0,
+            mined: 0,
+            mines_per_cell: 1,
+            stacks: Vec::new(),
+            clue_rule: ClueRule::Exact,
+            clue_neighbourhood: None,
+            clues: Vec::new(),
+        }
+    }
+
+    /*
+     *  Fonction qui change la façon dont les chiffres sont calculés et affichés
+     */
+    pub(crate) fn set_clue_rule(&mut self, rule: ClueRule, neighbourhood: Option<Neighbourhood>) {
+        self.clue_rule = rule;
+        self.clue_neighbourhood = neighbourhood;
+    }
+
+    /*
+     *  Fonction qui renvoie la règle d'affichage des chiffres
+     */
+    pub(crate) fn clue_rule(&self) -> ClueRule {
+        self.clue_rule
+    }
+
+    /*
+     *  Fonction qui indique si les chiffres affichés diffèrent du nombre de mines voisines
+     */
+    pub(crate) fn distorted_clues(&self) -> bool {
+        self.clue_rule != ClueRule::Exact || self.clue_neighbourhood.is_some()
+    }
+
+    /*
+     *  Fonction qui autorise jusqu'à `mines_per_cell` mines (et autant de drapeaux) par case
+     */
+    pub(crate) fn allow_stacks(&mut self, mines_per_cell: u8) {
+        // au-delà, le nombre de mines déborderait sur celui des drapeaux.
+        self.mines_per_cell = mines_per_cell.clamp(1, MAX_STACK);
+        if self.mines_per_cell > 1 {
+            self.stacks = vec![0; self.flags.len()];
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre maximal de mines d'une case
+     */
+    pub(crate) fn mines_per_cell(&self) -> usize {
+        self.mines_per_cell as usize
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre de cases contenant au moins une mine
+     */
+    pub(crate) fn mined_cells(&self) -> usize {
+        self.mined
+    }
+
+    /*
+     *  Fonction qui retire du plateau les cases non jouables d'un masque (le même pour chaque couche)
+     */
+    pub(crate) fn apply_mask(&mut self, playable: &[bool]) {
+        for row in 0..self.rows() {
+            for col in 0..self.size {
+                if !playable[(row % self.size) * self.size + col] && !self.is_masked(row, col) {
+                    self.set(row, col, MASKED);
+                    self.masked += 1;
+                }
+            }
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie la taille (côté) du plateau, ou d'une couche en 3D
+     */
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre de couches du plateau (1 pour un plateau plat)
+     */
+    pub(crate) fn layers(&self) -> usize {
+        self.layers
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre de lignes du plateau, toutes couches confondues
+     */
+    pub(crate) fn rows(&self) -> usize {
+        self.size * self.layers
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre total de cases du plateau
+     */
+    pub(crate) fn cell_count(&self) -> usize {
+        self.flags.len()
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre de cases jouables (hors masque) du plateau
+     */
+    pub(crate) fn playable_count(&self) -> usize {
+        self.flags.len() - self.masked
+    }
+
+    /*
+     *  Fonction qui renvoie la case centrale du plateau (au milieu de la couche du milieu)
+     */
+    pub(crate) fn center(&self) -> (usize, usize) {
+        let (row, col) = (self.layers / 2 * self.size + self.size / 2, self.size / 2);
+        if !self.is_masked(row, col) {
+            return (row, col);
+        }
+        // sur un plateau troué, on prend la case jouable la plus proche du centre.
+        (0..self.cell_count())
+            .map(|cell| (cell / self.size, cell % self.size))
+            .filter(|&(r, c)| !self.is_masked(r, c))
+            .min_by_key(|&(r, c)| r.abs_diff(row) + c.abs_diff(col))
+            .unwrap_or((row, col))
+    }
+
+    /*
+     *  Fonction qui renvoie la forme des cases du plateau
+     */
+    pub(crate) fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /*
+     *  Fonction qui indique si les bords du plateau se rejoignent
+     */
+    pub(crate) fn wraps(&self) -> bool {
+        self.neighbourhood.wraps()
+    }
+
+    /*
+     *  Fonction qui indique si les coordonnées sont dans le plateau
+     */
+    pub(crate) fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.rows() && col < self.size
+    }
+
+    /*
+     *  Fonction qui convertit des coordonnées en indice du tableau plat
+     */
+    fn index(&self, row: usize, col: usize) -> usize {
+        row * self.size + col
+    }
+
+    /*
+     *  Fonctions de lecture et d'écriture des drapeaux d'une case
+     */
+    pub(crate) fn has(&self, row: usize, col: usize, flag: u8) -> bool {
+        self.flags[self.index(row, col)] & flag != 0
+    }
+
+    pub(crate) fn set(&mut self, row: usize, col: usize, flag: u8) {
+        let index = self.index(row, col);
+        self.flags[index] |= flag;
+    }
+
+    pub(crate) fn clear(&mut self, row: usize, col: usize, flag: u8) {
+        let index = self.index(row, col);
+        self.flags[index] &= !flag;
+    }
+
+    pub(crate) fn is_mine(&self, row: usize, col: usize) -> bool {
+        self.has(row, col, MINE)
+    }
+
+    pub(crate) fn is_revealed(&self, row: usize, col: usize) -> bool {
+        self.has(row, col, REVEALED)
+    }
+
+    pub(crate) fn is_flagged(&self, row: usize, col: usize) -> bool {
+        self.has(row, col, FLAGGED)
+    }
+
+    pub(crate) fn is_masked(&self, row: usize, col: usize) -> bool {
+        self.has(row, col, MASKED)
+    }
+
+    /*
+     *  Fonctions qui renvoient le nombre de mines et de drapeaux d'une case
+     */
+    pub(crate) fn mines_at(&self, row: usize, col: usize) -> u8 {
+        match self.stacks.get(self.index(row, col)) {
+            Some(stack) => stack & 0x0F,
+            None => self.is_mine(row, col) as u8,
+        }
+    }
+
+    pub(crate) fn flags_at(&self, row: usize, col: usize) -> u8 {
+        match self.stacks.get(self.index(row, col)) {
+            Some(stack) => stack >> 4,
+            None => self.is_flagged(row, col) as u8,
+        }
+    }
+
+    /*
+     *  Fonction qui change le nombre de drapeaux posés sur une case
+     */
+    pub(crate) fn set_flags(&mut self, row: usize, col: usize, flags: u8) {
+        let index = self.index(row, col);
+        if let Some(stack) = self.stacks.get_mut(index) {
+            *stack = (*stack & 0x0F) | (flags << 4);
+        }
+        if flags > 0 {
+            self.set(row, col, FLAGGED);
+        } else {
+            self.clear(row, col, FLAGGED);
+        }
+    }
+
+    /*
+     *  Fonction qui renvoie le nombre (précalculé) de mines autour d'une case
+     */
+    pub(crate) fn count(&self, row: usize, col: usize) -> u8 {
+        self.counts[self.index(row, col)]
+    }
+
+    /*
+     *  Fonction qui renvoie le chiffre affiché sur une case révélée
+     */
+    pub(crate) fn clue(&self, row: usize, col: usize) -> u8 {
+        match self.clues.get(self.index(row, col)) {
+            Some(&clue) => clue,
+            None => self.count(row, col),
+        }
+    }
+
+    /*
+     *  Fonctions qui renvoient le nombre total de mines d'une ligne et d'une colonne du plateau
+     */
+    pub(crate) fn row_total(&self, row: usize) -> usize {
+        (0..self.size).map(|col| self.mines_at(row, col) as usize).sum()
+    }
+
+    pub(crate) fn col_total(&self, col: usize) -> usize {
+        (0..self.rows()).map(|row| self.mines_at(row, col) as usize).sum()
+    }
+
+    /*
+     *  Fonction qui renvoie les cases comptées par le chiffre d'une case
+     */
+    pub(crate) fn clue_area(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.clue_neighbourhood.as_ref().unwrap_or(&self.neighbourhood).around(row, col, self.size, self.layers)
+            .filter(move |&(r, c)| !self.is_masked(r, c))
+    }
+
+    /*
+     *  Fonction qui calcule les chiffres affichés, une fois toutes les mines posées
+     */
+    pub(crate) fn compute_clues(&mut self, rng: &mut impl Rng) {
+        if !self.distorted_clues() {
+            return;
+        }
+        // on part du vrai nombre de mines de la zone comptée, que la règle transforme.
+        self.clues = (0..self.cell_count())
+            .map(|cell| {
+                let (row, col) = (cell / self.size, cell % self.size);
+                let (mines, squares) = self.clue_area(row, col)
+                    .fold((0, 0), |(mines, squares), (r, c)| (mines + self.mines_at(r, c) as usize, squares + 1));
+                self.clue_rule.distort(mines, squares * self.mines_per_cell(), rng)
+            })
+            .collect();
+    }
+
+    /*
+     *  Fonction qui renvoie les voisins d'une case à l'intérieur du plateau, selon son voisinage
+     */
+    pub(crate) fn neighbours(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // les trous du masque ne sont les voisins de personne.
+        self.neighbourhood.around(row, col, self.size, self.layers)
+            .filter(move |&(r, c)| !self.is_masked(r, c))
+    }
+
+    /*
+     *  Fonction qui pose une mine (de plus) et met à jour le compteur de ses voisins
+     */
+    pub(crate) fn place_mine(&mut self, row: usize, col: usize) {
+        let mines = self.mines_at(row, col);
+        if mines as usize >= self.mines_per_cell() {
+            return;
+        }
+        if mines == 0 {
+            self.set(row, col, MINE);
+            self.mined += 1;
+        }
+        let index = self.index(row, col);
+        if let Some(stack) = self.stacks.get_mut(index) {
+            *stack += 1;
+        }
+        // le voisinage est symétrique : les voisines de la mine sont les cases qui la comptent
+        // (le compteur des trous est mis à jour aussi, mais n'est jamais lu).
+        for (r, c) in self.neighbourhood.around(row, col, self.size, self.layers) {
+            self.counts[r * self.size + c] += 1;
+        }
+    }
+}
+
+/*
+ *  Tests des cases à plusieurs mines
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stacked_grid(mines_per_cell: u8) -> Grid {
+        let mut grid = Grid::new(3, 1, Topology::Square, Neighbourhood::of(Topology::Square));
+        grid.allow_stacks(mines_per_cell);
+        grid
+    }
+
+    #[test]
+    fn mines_per_cell_is_capped_at_the_stack_size() {
+        assert_eq!(stacked_grid(20).mines_per_cell(), MAX_STACK as usize);
+        assert_eq!(stacked_grid(0).mines_per_cell(), 1);
+    }
+
+    #[test]
+    fn mines_never_spill_into_flags() {
+        let mut grid = stacked_grid(20);
+        for _ in 0..17 {
+            grid.place_mine(1, 1);
+        }
+        assert_eq!(grid.mines_at(1, 1), MAX_STACK);
+        assert_eq!(grid.flags_at(1, 1), 0);
+        assert_eq!(grid.mined_cells(), 1);
+        // chaque voisine compte les mines effectivement posées, pas les tentatives.
+        assert_eq!(grid.counts[0], MAX_STACK);
+
+        grid.set_flags(1, 1, MAX_STACK);
+        assert_eq!((grid.mines_at(1, 1), grid.flags_at(1, 1)), (MAX_STACK, MAX_STACK));
+        grid.set_flags(1, 1, 0);
+        assert_eq!((grid.mines_at(1, 1), grid.flags_at(1, 1)), (MAX_STACK, 0));
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ *      Nom du fichier : mask.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::fs;
+
+use serde_derive::{Deserialize, Serialize};
+
+/*
+ *  Définition des caractères d'un masque
+ */
+// case jouable du plateau.
+const SQUARE: char = '#';
+// trou : case absente du plateau.
+const HOLES: [char; 2] = ['.', ' '];
+
+/*
+ *  Définition des Énumérations
+ */
+// Forme d'un plateau : dessin en ASCII, écrit dans la configuration ou dans un fichier à part.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum MaskSource {
+    Inline(Vec<String>),
+    File(String),
+}
+
+/*
+ *  Implémentation du chargement d'un masque
+ */
+impl MaskSource {
+    /*
+     *  Fonction qui renvoie, pour chaque case d'un plateau de côté `size`, si elle est jouable
+     */
+    pub(crate) fn load(&self, size: usize) -> Result<Vec<bool>, String> {
+        match self {
+            MaskSource::Inline(lines) => parse(lines.iter().map(String::as_str), size),
+            MaskSource::File(path) => {
+                let content = fs::read_to_string(path).map_err(|err| format!("cannot read the mask '{}' : {}", path, err))?;
+                parse(content.lines(), size)
+            }
+        }
+    }
+}
+
+/*
+ *  Fonction qui lit un dessin ligne par ligne : '#' pour une case, '.' ou ' ' pour un trou
+ *  (ce qui dépasse le dessin, à droite ou en bas, est un trou)
+ */
+fn parse<'a>(lines: impl Iterator<Item = &'a str>, size: usize) -> Result<Vec<bool>, String> {
+    let mut playable = vec![false; size * size];
+    for (row, line) in lines.enumerate() {
+        for (col, symbol) in line.chars().enumerate() {
+            if HOLES.contains(&symbol) {
+                continue;
+            }
+            if symbol != SQUARE {
+                return Err(format!("unexpected character '{}' in the mask (use '{}' for a square, '.' or ' ' for a hole)", symbol, SQUARE));
+            }
+            if row >= size || col >= size {
+                return Err(format!("the mask does not fit in a {}x{} board", size, size));
+            }
+            playable[row * size + col] = true;
+        }
+    }
+    Ok(playable)
+}
+
+/*
+ *  Tests de la lecture des masques
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squares_and_holes() {
+        let playable = parse(["#.#", " # ", "###"].into_iter(), 3).unwrap();
+        assert_eq!(playable, [true, false, true, false, true, false, true, true, true]);
+    }
+
+    #[test]
+    fn short_drawings_leave_holes_on_the_right_and_below() {
+        let playable = parse(["##", "#"].into_iter(), 3).unwrap();
+        assert_eq!(playable, [true, true, false, true, false, false, false, false, false]);
+        assert_eq!(parse(std::iter::empty(), 2).unwrap(), [false; 4]);
+    }
+
+    #[test]
+    fn trailing_holes_may_go_past_the_board() {
+        assert!(parse(["##...", "##", "  ", ".."].into_iter(), 2).is_ok());
+    }
+
+    #[test]
+    fn squares_outside_the_board_are_rejected() {
+        assert_eq!(parse(["###"].into_iter(), 2).unwrap_err(), "the mask does not fit in a 2x2 board");
+        assert_eq!(parse(["##", "##", "#"].into_iter(), 2).unwrap_err(), "the mask does not fit in a 2x2 board");
+    }
+
+    #[test]
+    fn unknown_characters_are_rejected() {
+        let err = parse(["#x"].into_iter(), 2).unwrap_err();
+        assert!(err.starts_with("unexpected character 'x'"), "{}", err);
+    }
+
+    #[test]
+    fn inline_masks_are_loaded_like_files() {
+        let source = MaskSource::Inline(vec!["#.".to_string(), ".#".to_string()]);
+        assert_eq!(source.load(2).unwrap(), [true, false, false, true]);
+        assert!(MaskSource::File("no/such/mask.txt".to_string()).load(2).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ *      Nom du fichier : neighbourhood.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use serde_derive::{Deserialize, Serialize};
+
+/*
+ *  Définition des voisinages prédéfinis (décalages ligne, colonne)
+ */
+const SQUARE_OFFSETS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+// grille hexagonale en lignes décalées : les lignes impaires sont décalées d'une demi-case vers la droite.
+const HEX_EVEN_ROW_OFFSETS: [(isize, isize); 6] = [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+const HEX_ODD_ROW_OFFSETS: [(isize, isize); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
+
+/*
+ *  Définition des Structures et des Énumérations
+ */
+// Liste de décalages (ligne, colonne) vers les voisines d'une case.
+type Offsets = [(isize, isize)];
+// Décalage (couche, ligne, colonne) vers une voisine.
+type Offset3 = (isize, isize, isize);
+
+// Forme des cases du plateau, qui détermine leur affichage et leurs voisines par défaut.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Topology {
+    #[default]
+    Square,
+    Hex,
+    // cases carrées dont les bords opposés se touchent (tore).
+    Torus,
+}
+
+// Voisinage d'une case : décalages vers ses voisines, selon la parité de sa ligne.
+// les décalages portent aussi sur la couche, qui vaut toujours 0 pour un plateau plat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Neighbourhood {
+    even_rows: Vec<Offset3>,
+    odd_rows: Vec<Offset3>,
+    wrap: bool,
+}
+
+/*
+ *  Fonction qui place des décalages plats sur la couche de la case
+ */
+fn flat(offsets: &Offsets) -> Vec<Offset3> {
+    offsets.iter().map(|&(delta_row, delta_col)| (0, delta_row, delta_col)).collect()
+}
+
+/*
+ *  Fonction qui décale une coordonnée, en faisant le tour du plateau si besoin
+ */
+fn shift(position: usize, delta: isize, size: usize, wrap: bool) -> Option<usize> {
+    if wrap {
+        Some((position as isize + delta).rem_euclid(size as isize) as usize)
+    } else {
+        position.checked_add_signed(delta).filter(|&shifted| shifted < size)
+    }
+}
+
+/*
+ *  Implémentation du voisinage
+ */
+impl Neighbourhood {
+    /*
+     *  Fonction qui renvoie le voisinage habituel d'une forme de plateau
+     */
+    pub(crate) fn of(topology: Topology) -> Self {
+        let (even_rows, odd_rows): (&Offsets, &Offsets) = match topology {
+            Topology::Square | Topology::Torus => (&SQUARE_OFFSETS, &SQUARE_OFFSETS),
+            Topology::Hex => (&HEX_EVEN_ROW_OFFSETS, &HEX_ODD_ROW_OFFSETS),
+        };
+        Neighbourhood { even_rows: flat(even_rows), odd_rows: flat(odd_rows), wrap: topology == Topology::Torus }
+    }
+
+    /*
+     *  Fonction qui renvoie le voisinage d'un plateau en 3D (les 26 cases du cube 3x3x3)
+     */
+    pub(crate) fn cube() -> Self {
+        let offsets: Vec<Offset3> = (-1..=1)
+            .flat_map(|delta_layer| (-1..=1).flat_map(move |delta_row| (-1..=1).map(move |delta_col| (delta_layer, delta_row, delta_col))))
+            .filter(|&offset| offset != (0, 0, 0))
+            .collect();
+        Neighbourhood { even_rows: offsets.clone(), odd_rows: offsets, wrap: false }
+    }
+
+    /*
+     *  Fonction qui crée un voisinage personnalisé (le même pour toutes les lignes)
+     */
+    pub(crate) fn custom(offsets: &Offsets, topology: Topology) -> Result<Self, String> {
+        if offsets.is_empty() {
+            return Err("a neighbourhood needs at least one offset".to_string());
+        }
+        for (position, &(delta_row, delta_col)) in offsets.iter().enumerate() {
+            if (delta_row, delta_col) == (0, 0) {
+                return Err("a square cannot be its own neighbour".to_string());
+            }
+            if offsets[..position].contains(&(delta_row, delta_col)) {
+                return Err(format!("the offset [{}, {}] is listed twice", delta_row, delta_col));
+            }
+            // un chiffre compte les mines de ses voisines : le voisinage doit être symétrique
+            // pour que les voisines d'une mine soient exactement les cases qui la comptent.
+            if !offsets.contains(&(-delta_row, -delta_col)) {
+                return Err(format!("the offset [{}, {}] has no opposite [{}, {}]", delta_row, delta_col, -delta_row, -delta_col));
+            }
+        }
+        Ok(Neighbourhood { even_rows: flat(offsets), odd_rows: flat(offsets), wrap: topology == Topology::Torus })
+    }
+
+    /*
+     *  Fonction qui vérifie que le voisinage tient sur un plateau de côté `board_size`
+     */
+    pub(crate) fn fits(&self, board_size: usize) -> Result<(), String> {
+        // sur un tore, deux décalages ne doivent pas désigner la même case (ni la case elle-même).
+        let reach = self.even_rows.iter().chain(&self.odd_rows)
+            .map(|&(_, delta_row, delta_col)| delta_row.unsigned_abs().max(delta_col.unsigned_abs()))
+            .max()
+            .unwrap_or(0);
+        if self.wrap && board_size <= 2 * reach {
+            return Err(format!("a wrap-around board must be larger than {} squares for this neighbourhood", 2 * reach));
+        }
+        Ok(())
+    }
+
+    /*
+     *  Fonction qui renvoie le plus grand nombre de voisines d'une case
+     */
+    pub(crate) fn max_neighbours(&self) -> usize {
+        self.even_rows.len().max(self.odd_rows.len())
+    }
+
+    /*
+     *  Fonction qui indique si les bords du plateau se rejoignent
+     */
+    pub(crate) fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    /*
+     *  Fonction qui renvoie les voisines d'une case à l'intérieur d'un plateau de `layers` couches de côté `size`
+     */
+    pub(crate) fn around(&self, row: usize, col: usize, size: usize, layers: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // on sépare la couche de la ligne à l'intérieur de la couche (les couches ne se rejoignent jamais).
+        let (layer, layer_row) = (row / size, row % size);
+        let offsets = if layer_row % 2 == 1 { &self.odd_rows } else { &self.even_rows };
+        offsets.iter().filter_map(move |&(delta_layer, delta_row, delta_col)| {
+            let neighbour_layer = shift(layer, delta_layer, layers, false)?;
+            let neighbour_row = shift(layer_row, delta_row, size, self.wrap)?;
+            Some((neighbour_layer * size + neighbour_row, shift(col, delta_col, size, self.wrap)?))
+        })
+    }
+}
+
+/*
+ *  Tests des voisinages
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn torus_needs_a_board_larger_than_twice_its_reach() {
+        let torus = Neighbourhood::of(Topology::Torus);
+        assert!(torus.fits(1).is_err());
+        assert!(torus.fits(2).is_err());
+        assert!(torus.fits(3).is_ok());
+
+        let knight = Neighbourhood::custom(&[(1, 2), (-1, -2), (2, -1), (-2, 1)], Topology::Torus).unwrap();
+        assert!(knight.fits(4).is_err());
+        assert!(knight.fits(5).is_ok());
+    }
+
+    #[test]
+    fn boards_without_wrapping_fit_any_size() {
+        assert!(Neighbourhood::of(Topology::Square).fits(1).is_ok());
+        assert!(Neighbourhood::of(Topology::Hex).fits(1).is_ok());
+        assert!(Neighbourhood::cube().fits(1).is_ok());
+    }
+
+    #[test]
+    fn torus_neighbours_are_distinct_once_the_board_fits() {
+        let torus = Neighbourhood::of(Topology::Torus);
+        let mut neighbours: Vec<_> = torus.around(0, 0, 3, 1).collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        assert_eq!(neighbours.len(), 8);
+        assert!(!neighbours.contains(&(0, 0)));
+    }
+
+    #[test]
+    fn custom_neighbourhoods_reject_invalid_offsets() {
+        let rejected = |offsets: &[(isize, isize)]| Neighbourhood::custom(offsets, Topology::Square).unwrap_err();
+        assert_eq!(rejected(&[]), "a neighbourhood needs at least one offset");
+        assert_eq!(rejected(&[(0, 1), (0, 0), (0, -1)]), "a square cannot be its own neighbour");
+        assert_eq!(rejected(&[(0, 1), (0, -1), (0, 1)]), "the offset [0, 1] is listed twice");
+        assert_eq!(rejected(&[(0, 1), (0, -1), (2, 1)]), "the offset [2, 1] has no opposite [-2, -1]");
+    }
+
+    #[test]
+    fn custom_neighbourhoods_keep_their_offsets() {
+        let cross = Neighbourhood::custom(&[(-1, 0), (1, 0), (0, -1), (0, 1)], Topology::Square).unwrap();
+        assert_eq!(cross.max_neighbours(), 4);
+        assert!(!cross.wraps());
+        let mut neighbours: Vec<_> = cross.around(0, 0, 5, 1).collect();
+        neighbours.sort_unstable();
+        assert_eq!(neighbours, [(0, 1), (1, 0)]);
+        assert!(Neighbourhood::custom(&[(-1, 0), (1, 0)], Topology::Torus).unwrap().wraps());
+    }
+}
@@ -0,0 +1,275 @@
+/*
+ *      Nom du fichier : protocol.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Protocole des robots : une ligne JSON par message, sur l'entrée et la sortie standard.
+ *
+ *  Le programme envoie d'abord l'état du plateau :
+ *      {"type":"state","mode":"Small","layers":1,"rows":8,"cols":8,"mines":10,"status":"playing","moves":0,"board":[[".",...],...]}
+ *  puis répond à chaque action reçue :
+ *      {"action":"reveal","row":2,"col":3}      (aussi "flag" et "chord", avec "layer" sur les plateaux 3D)
+ *      {"action":"state"}                       renvoie l'état du plateau
+ *      {"action":"quit"}                        termine la partie
+ *  par un résultat (ou une erreur, sans effet sur la partie) :
+ *      {"type":"result","square":{"row":2,"col":3,"value":"1"},"revealed":[...],"status":"playing","moves":1}
+ *      {"type":"error","error":"..."}
+ *
+ *  Les cases sont écrites comme dans les parties scriptées ('.' cachée, '*' vide, 'F' drapeau, 'M' mine...) ;
+ *  en 3D, les couches du plateau sont empilées les unes sous les autres dans "board".
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::io::{self, BufRead};
+
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::game::{Action, BoardInfo, GameActions, Minesweeper};
+use super::command::Position;
+use super::display;
+
+/*
+ *  Définition des Structures et des Énumérations
+ */
+// Action envoyée par le robot.
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum Request {
+    Reveal { layer: Option<usize>, row: usize, col: usize },
+    Flag { layer: Option<usize>, row: usize, col: usize },
+    Chord { layer: Option<usize>, row: usize, col: usize },
+    State,
+    Quit,
+}
+
+// État de la partie vu par le robot.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Playing,
+    Won,
+    Lost,
+}
+
+// Case visible du plateau (la couche n'est donnée que sur les plateaux 3D).
+#[derive(Serialize)]
+struct Square {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layer: Option<usize>,
+    row: usize,
+    col: usize,
+    value: String,
+}
+
+// Message envoyé au robot.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    State {
+        mode: String,
+        layers: usize,
+        rows: usize,
+        cols: usize,
+        mines: usize,
+        status: Status,
+        moves: usize,
+        board: Vec<Vec<String>>,
+    },
+    Result {
+        square: Square,
+        revealed: Vec<Square>,
+        status: Status,
+        moves: usize,
+        // message du jeu lorsqu'il refuse le coup (case déjà révélée, chiffre sans assez de drapeaux...).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    Error {
+        error: String,
+    },
+}
+
+/*
+ *  Fonction qui renvoie l'état de la partie
+ */
+fn status(game: &mut Minesweeper) -> Status {
+    if game.game_over {
+        Status::Lost
+    } else if game.check_win() {
+        Status::Won
+    } else {
+        Status::Playing
+    }
+}
+
+/*
+ *  Fonction qui décrit une case du plateau, avec sa couche en 3D
+ */
+fn square(game: &Minesweeper, row: usize, col: usize) -> Square {
+    let size = game.grid.size();
+    let layer = (game.grid.layers() > 1).then_some(row / size);
+    Square { layer, row: row % size, col, value: display::plain_cell(game, row, col) }
+}
+
+/*
+ *  Fonction qui renvoie l'état complet du plateau
+ */
+fn state(game: &mut Minesweeper) -> Response {
+    let board = (0..game.grid.rows())
+        .map(|row| (0..game.grid.size()).map(|col| display::plain_cell(game, row, col)).collect())
+        .collect();
+    Response::State {
+        mode: game.configuration.name.clone(),
+        layers: game.grid.layers(),
+        rows: game.grid.size(),
+        cols: game.grid.size(),
+        mines: game.configuration.num_mines,
+        status: status(game),
+        moves: game.moves,
+        board,
+    }
+}
+
+/*
+ *  Fonction qui joue une action du robot sur une case et décrit son résultat
+ */
+fn play_at(game: &mut Minesweeper, position: Position, action: fn(usize, usize) -> Action) -> Response {
+    if status(game) != Status::Playing {
+        return Response::Error { error: "The game is over.".to_string() };
+    }
+    let (row, col) = match game.resolve(position) {
+        Ok(square) => square,
+        Err(err) => return Response::Error { error: err.trim_end_matches("...").to_string() },
+    };
+    // une case hors du plateau est une erreur du robot, qui ne compte pas comme un coup.
+    if !game.grid.contains(row, col) || game.grid.is_masked(row, col) {
+        return Response::Error { error: "The square is not on the board.".to_string() };
+    }
+
+    game.player_message = None;
+    let revealed = game.apply_action(action(row, col));
+    Response::Result {
+        square: square(game, row, col),
+        revealed: revealed.into_iter().map(|(row, col)| square(game, row, col)).collect(),
+        status: status(game),
+        moves: game.moves,
+        message: game.player_message.take().filter(|message| !message.is_empty()).map(|message| message.trim_end_matches("...").to_string()),
+    }
+}
+
+/*
+ *  Fonction qui répond à une ligne envoyée par le robot (rien s'il quitte la partie)
+ */
+fn respond(game: &mut Minesweeper, line: &str) -> Option<Response> {
+    let response = match serde_json::from_str(line) {
+        Ok(Request::Reveal { layer, row, col }) => play_at(game, Position { layer, row, col }, Action::Reveal),
+        Ok(Request::Flag { layer, row, col }) => play_at(game, Position { layer, row, col }, Action::Mark),
+        Ok(Request::Chord { layer, row, col }) => play_at(game, Position { layer, row, col }, Action::Chord),
+        Ok(Request::State) => state(game),
+        Ok(Request::Quit) => return None,
+        Err(err) => Response::Error { error: format!("Invalid action : {}", err) },
+    };
+    Some(response)
+}
+
+/*
+ *  Fonction qui envoie un message au robot, sur une ligne
+ */
+fn send(response: &Response) {
+    match serde_json::to_string(response) {
+        Ok(line) => println!("{}", line),
+        Err(err) => eprintln!("The message could not be written : {}", err),
+    }
+}
+
+/*
+ *  Fonction qui fait jouer un robot par le protocole JSON, jusqu'à ce qu'il quitte ou ferme l'entrée standard
+ */
+pub(crate) fn run(board_info: BoardInfo, seed: Option<u64>) {
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut game = Minesweeper::with_seed(board_info, seed);
+    send(&state(&mut game));
+
+    for line in io::stdin().lock().lines() {
+        // une erreur de lecture est traitée comme la fin de l'entrée.
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match respond(&mut game, &line) {
+            Some(response) => send(&response),
+            None => break,
+        }
+    }
+}
+
+/*
+ *  Tests du protocole des robots
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    // réponse du jeu à une ligne du robot, telle qu'il la lit.
+    fn exchange(game: &mut Minesweeper, line: &str) -> Value {
+        let response = respond(game, line).expect("the game answers every action but 'quit'");
+        serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap()
+    }
+
+    fn game() -> Minesweeper {
+        Minesweeper::with_seed(BoardInfo::classic(8, 10), 3)
+    }
+
+    #[test]
+    fn state_describes_the_whole_board() {
+        let state = exchange(&mut game(), r#"{"action":"state"}"#);
+        assert_eq!(state["type"], "state");
+        assert_eq!((state["rows"].clone(), state["cols"].clone(), state["mines"].clone()), (json!(8), json!(8), json!(10)));
+        assert_eq!((state["status"].clone(), state["moves"].clone()), (json!("playing"), json!(0)));
+        assert_eq!(state["board"], json!(vec![vec!["."; 8]; 8]));
+        assert!(state.get("layers").is_some());
+    }
+
+    #[test]
+    fn reveal_answers_with_the_squares_it_opened() {
+        let mut game = game();
+        let result = exchange(&mut game, r#"{"action":"reveal","row":0,"col":0}"#);
+        assert_eq!(result["type"], "result");
+        assert_eq!(result["square"], json!({ "row": 0, "col": 0, "value": display::plain_cell(&game, 0, 0) }));
+        assert_eq!(result["moves"], 1);
+        assert!(!result["revealed"].as_array().unwrap().is_empty());
+        assert!(result.get("message").is_none());
+
+        // un coup refusé par le jeu est un résultat avec un message, pas une erreur.
+        let again = exchange(&mut game, r#"{"action":"flag","row":0,"col":0}"#);
+        assert_eq!((again["type"].clone(), again["moves"].clone()), (json!("result"), json!(1)));
+        assert!(again["message"].is_string());
+    }
+
+    #[test]
+    fn invalid_requests_are_errors_without_effect() {
+        let mut game = game();
+        for line in [r#"{"action":"dig","row":0,"col":0}"#, r#"{"action":"reveal","row":0}"#, "not json", r#"{"action":"reveal","row":8,"col":0}"#] {
+            let error = exchange(&mut game, line);
+            assert_eq!(error["type"], "error", "{}", line);
+            assert!(error["error"].is_string());
+        }
+        let layered = exchange(&mut game, r#"{"action":"reveal","layer":1,"row":0,"col":0}"#);
+        assert_eq!(layered["type"], "error");
+        assert_eq!(game.moves, 0);
+    }
+
+    #[test]
+    fn quit_ends_the_exchange() {
+        assert!(respond(&mut game(), r#"{"action":"quit"}"#).is_none());
+    }
+}
@@ -13,26 +13,84 @@
 use std::io;
 use std::io::BufReader;
 use std::fs::File;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
 use crate::game::Config; 
 
 /*
- *  Fonction pour lire la saisie du joueur
+ *  Définition des constantes
  */
-pub fn read_user_input(input: &mut String) {
-        loop {
-                // on récupère la saisie du joueur
-                let result = io::stdin().read_line(input);
+// intervalle de rafraîchissement d'un compte à rebours pendant l'attente d'une saisie.
+const TICK: Duration = Duration::from_secs(1);
+
+// lignes saisies par le joueur, lues en arrière-plan pour pouvoir les attendre avec une limite de temps.
+static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
 
-                match result {
-                        Ok(_) => {
-                                // on sort de la boucle, la saisie a été lue avec succès.
-                                break;
+/*
+ *  Fonction qui renvoie les lignes saisies, en lançant la lecture en arrière-plan au premier appel
+ */
+fn lines() -> MutexGuard<'static, Receiver<String>> {
+        LINES.get_or_init(|| {
+                let (sender, receiver) = mpsc::channel();
+                thread::spawn(move || loop {
+                        let mut line = String::new();
+                        match io::stdin().read_line(&mut line) {
+                                // fin de l'entrée : le joueur ne saisira plus rien.
+                                Ok(0) => break,
+                                Ok(_) => {
+                                        if sender.send(line).is_err() {
+                                                break;
+                                        }
+                                }
+                                // on affiche un message d'erreur : l'entrée est alors considérée comme terminée,
+                                // pour ne pas relire indéfiniment une entrée en erreur.
+                                Err(error) => {
+                                        eprintln!("Failed to read input: {}", error);
+                                        break;
+                                }
                         }
-                        Err(error) => {
-                                // on affiche un message d'erreur, une erreur s'est produite.
-                                eprintln!("Failed to read input: {}", error);
-                                continue;
+                });
+                Mutex::new(receiver)
+        }).lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/*
+ *  Fonction pour lire la saisie du joueur
+ *  (renvoie faux à la fin de l'entrée, quand le joueur ne peut plus rien saisir)
+ */
+pub fn read_user_input(input: &mut String) -> bool {
+        // on récupère la saisie du joueur (rien à la fin de l'entrée).
+        match lines().recv() {
+                Ok(line) => {
+                        input.push_str(&line);
+                        true
+                }
+                Err(_) => false,
+        }
+}
+
+/*
+ *  Fonction pour lire la saisie du joueur avant une échéance, en signalant le temps restant à chaque seconde
+ *  (renvoie faux si le temps est écoulé avant la saisie)
+ */
+pub(crate) fn read_user_input_until(input: &mut String, deadline: Instant, mut on_tick: impl FnMut(Duration)) -> bool {
+        let lines = lines();
+        loop {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                        return false;
+                }
+                on_tick(left);
+                match lines.recv_timeout(left.min(TICK)) {
+                        Ok(line) => {
+                                input.push_str(&line);
+                                return true;
                         }
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        // fin de l'entrée : comme pour la lecture sans échéance, la saisie reste vide.
+                        Err(RecvTimeoutError::Disconnected) => return true,
                 }
         }
 }
@@ -47,6 +105,8 @@ pub fn read_config_from_file(file_path: &str) -> Result<Config, Box<dyn std::err
         let reader = BufReader::new(file);
         // on désérialise le contenu JSON dans la structure Config.
         let config: Config = serde_json::from_reader(reader)?;
+        // on vérifie que les modes de jeu sont jouables (voisinages personnalisés notamment).
+        config.validate()?;
         // on retourne la configuration lue depuis le fichier.
         Ok(config)
 }
\ No newline at end of file
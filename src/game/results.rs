@@ -0,0 +1,81 @@
+/*
+ *      Nom du fichier : results.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::Duration;
+
+use serde_derive::Serialize;
+
+/*
+ *  Définition des constantes
+ */
+// fichier où sont ajoutés les résultats des parties jouées (un objet JSON par ligne).
+pub(crate) const RESULTS_PATH: &str = "minesweeper_results.jsonl";
+
+/*
+ *  Définition des Structures et des Énumérations
+ */
+// Issue d'une partie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Outcome {
+    Won,
+    Lost,
+    // le temps imparti s'est écoulé avant la fin de la partie.
+    TimedOut,
+    // le joueur a quitté la partie avant sa fin.
+    Abandoned,
+}
+
+// Résultat d'une partie jouée par le joueur.
+#[derive(Serialize)]
+pub(crate) struct GameResult {
+    pub(crate) mode: String,
+    pub(crate) seed: u64,
+    pub(crate) outcome: Outcome,
+    pub(crate) seconds: f64,
+    pub(crate) lives_lost: usize,
+    // victoire sans avoir perdu de vie.
+    pub(crate) perfect: bool,
+    // partie en mode de mémoire, comptée à part des parties où les chiffres restent affichés.
+    pub(crate) blind: bool,
+    // partie aidée (robot, indice) : jamais parfaite, à écarter des records.
+    pub(crate) assisted: bool,
+}
+
+/*
+ *  Implémentation de l'enregistrement des résultats
+ */
+impl GameResult {
+    pub(crate) fn new(mode: &str, seed: u64, outcome: Outcome, elapsed: Duration, lives_lost: usize, blind: bool, assisted: bool) -> Self {
+        GameResult {
+            mode: mode.to_string(),
+            seed,
+            outcome,
+            seconds: elapsed.as_secs_f64(),
+            lives_lost,
+            perfect: outcome == Outcome::Won && lives_lost == 0 && !assisted,
+            blind,
+            assisted,
+        }
+    }
+
+    /*
+     *  Fonction qui ajoute le résultat à la fin du fichier des résultats
+     */
+    pub(crate) fn record(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
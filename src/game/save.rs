@@ -0,0 +1,72 @@
+/*
+ *      Nom du fichier : save.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::game::{Action, Minesweeper};
+
+/*
+ *  Définition des constantes
+ */
+// fichier de sauvegarde de la partie en cours.
+pub(crate) const SAVE_PATH: &str = "minesweeper_save.json";
+
+/*
+ *  Définition des Structures
+ */
+// Partie sauvegardée : le plateau se régénère à partir de la graine, on ne garde que les coups du joueur.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SavedGame {
+    pub(crate) mode: String,
+    pub(crate) seed: u64,
+    pub(crate) actions: Vec<Action>,
+    // les sauvegardes antérieures ne notaient pas l'aide reçue.
+    #[serde(default)]
+    pub(crate) assisted: bool,
+}
+
+/*
+ *  Implémentation de la sauvegarde d'une partie
+ */
+impl SavedGame {
+    pub(crate) fn of(game: &Minesweeper) -> Self {
+        SavedGame { mode: game.configuration.name.clone(), seed: game.seed, actions: game.actions.clone(), assisted: game.assisted }
+    }
+
+    /*
+     *  Fonction qui écrit la partie dans un fichier JSON
+     */
+    pub(crate) fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(BufWriter::new(File::create(path)?), self)?;
+        Ok(())
+    }
+
+    /*
+     *  Fonction qui relit une partie sauvegardée, en la retirant du disque (une sauvegarde ne se reprend qu'une fois)
+     */
+    pub(crate) fn take(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let saved: SavedGame = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        fs::remove_file(path)?;
+        Ok(saved)
+    }
+
+    /*
+     *  Fonction qui lit seulement le mode de la partie sauvegardée, s'il y en a une
+     */
+    pub(crate) fn mode_of(path: &str) -> Option<String> {
+        let saved: SavedGame = serde_json::from_reader(BufReader::new(File::open(path).ok()?)).ok()?;
+        Some(saved.mode)
+    }
+}
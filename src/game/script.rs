@@ -0,0 +1,246 @@
+/*
+ *      Nom du fichier : script.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::fs;
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+use rand::Rng;
+
+use crate::game::{Action, BoardInfo, GameActions, Minesweeper};
+use super::command::{self, Command, Position};
+use super::{autoplay, display};
+
+/*
+ *  Définition des constantes
+ */
+// codes de sortie d'une partie scriptée.
+const EXIT_WON: u8 = 0;
+const EXIT_LOST: u8 = 1;
+const EXIT_INVALID: u8 = 2;
+const EXIT_UNFINISHED: u8 = 3;
+
+/*
+ *  Définition des Structures
+ */
+// Paramètres d'une partie scriptée.
+pub(crate) struct ScriptOptions {
+    pub(crate) index: u32,
+    // fichier des coups (l'entrée standard par défaut).
+    pub(crate) file: Option<String>,
+    pub(crate) seed: Option<u64>,
+    // affichage du plateau après chaque coup, et pas seulement à la fin.
+    pub(crate) verbose: bool,
+}
+
+/*
+ *  Fonction qui joue une action sur une case du script
+ */
+fn play_at(game: &mut Minesweeper, position: Position, action: fn(usize, usize) -> Action) -> Result<(), String> {
+    // une case hors du plateau rend le script invalide (au lieu d'un simple message au joueur).
+    let (row, col) = game.resolve(position)?;
+    if !game.grid.contains(row, col) || game.grid.is_masked(row, col) {
+        // on nomme la case telle qu'elle est écrite dans le script (resolve déplace les lignes trop grandes).
+        let label = match position.layer {
+            Some(layer) => format!("{},{},{}", layer, position.row, position.col),
+            None => game.notation.square(position.row, position.col),
+        };
+        return Err(format!("{} is not on the board", label));
+    }
+    game.apply_action(action(row, col));
+    Ok(())
+}
+
+/*
+ *  Fonction qui exécute une commande du script (renvoie faux si le script demande d'arrêter la partie)
+ */
+fn run_command(game: &mut Minesweeper, command: Command) -> Result<bool, String> {
+    match command {
+        Command::Reveal(position) => play_at(game, position, Action::Reveal)?,
+        Command::Flag(position) => play_at(game, position, Action::Mark)?,
+        Command::Chord(position) => play_at(game, position, Action::Chord)?,
+        Command::Goto(position) => {
+            let (row, col) = game.resolve(position)?;
+            game.jump_to(row, col);
+        }
+        // sans affichage interactif, le défilement, l'aide et la sauvegarde n'ont pas d'effet.
+        Command::Scroll(..) | Command::Layer(_) | Command::Help | Command::Save => {}
+        Command::Hint => game.hint(),
+        Command::Undo => game.undo(),
+        Command::Auto => {
+            autoplay::finish_game(game, None);
+        }
+        Command::Quit | Command::Menu | Command::Restart | Command::Retry => return Ok(false),
+    }
+    Ok(true)
+}
+
+/*
+ *  Fonction qui décrit un coup du script, pour l'affichage pas à pas (rien pour les commandes sans effet sur le plateau)
+ */
+fn describe(game: &Minesweeper, command: Command) -> Option<String> {
+    let at = |verb: &str, position| game.resolve(position).ok().map(|(row, col)| format!("{} {}", verb, game.position_label(row, col)));
+    match command {
+        Command::Reveal(position) => at("reveal", position),
+        Command::Flag(position) => at("flag", position),
+        Command::Chord(position) => at("chord", position),
+        Command::Hint => Some("hint".to_string()),
+        Command::Undo => Some("undo".to_string()),
+        Command::Auto => Some("auto".to_string()),
+        _ => None,
+    }
+}
+
+/*
+ *  Fonction qui joue les coups d'un script jusqu'à la fin de la partie ou du script
+ *  (renvoie le numéro et l'erreur de la première ligne invalide)
+ */
+fn run_lines(game: &mut Minesweeper, lines: impl Iterator<Item = io::Result<String>>, verbose: bool) -> Result<(), (usize, String)> {
+    for (number, line) in lines.enumerate().map(|(index, line)| (index + 1, line)) {
+        let line = line.map_err(|err| (number, err.to_string()))?;
+        // les lignes vides et les commentaires ('#') sont ignorés.
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let commands = command::parse(&line).map_err(|err| (number, err.to_string()))?;
+        for command in commands {
+            let description = describe(game, command);
+            if !run_command(game, command).map_err(|err| (number, err))? {
+                return Ok(());
+            }
+
+            // en mode pas à pas, on affiche le plateau après chaque coup (une ligne peut en contenir plusieurs).
+            if let Some(description) = description.filter(|_| verbose) {
+                println!("> {} (line {})", description, number);
+                if let Some(message) = game.player_message.take().filter(|message| !message.is_empty()) {
+                    println!("{}", message);
+                }
+                display::print_plain_board(game);
+                println!();
+            }
+            // on s'arrête au premier coup qui termine la partie, sans lire la suite du script.
+            if game.game_over || game.check_win() {
+                break;
+            }
+        }
+        if game.game_over || game.check_win() {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+/*
+ *  Fonction qui renvoie l'issue d'une partie scriptée et son code de sortie
+ */
+fn outcome(game: &mut Minesweeper) -> (&'static str, u8) {
+    if game.game_over {
+        ("lost", EXIT_LOST)
+    } else if game.check_win() {
+        ("won", EXIT_WON)
+    } else {
+        ("unfinished", EXIT_UNFINISHED)
+    }
+}
+
+/*
+ *  Fonction qui joue une partie à partir d'un script de coups, sans interaction
+ *  (code de sortie : 0 gagnée, 1 perdue, 2 script invalide, 3 partie inachevée)
+ */
+pub(crate) fn run(board_info: BoardInfo, options: &ScriptOptions) -> ExitCode {
+    let seed = options.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut game = Minesweeper::with_seed(board_info, seed);
+
+    // on lit le script depuis un fichier, ou depuis l'entrée standard jusqu'à sa fin.
+    let result = match &options.file {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(script) => run_lines(&mut game, script.lines().map(|line| Ok(line.to_string())), options.verbose),
+            Err(err) => {
+                eprintln!("The script '{}' could not be read : {}", path, err);
+                return ExitCode::from(EXIT_INVALID);
+            }
+        },
+        None => run_lines(&mut game, io::stdin().lock().lines(), options.verbose),
+    };
+    if let Err((line, err)) = result {
+        eprintln!("Invalid script, line {} : {}", line, err.trim_end_matches("..."));
+        return ExitCode::from(EXIT_INVALID);
+    }
+
+    // on affiche le plateau final et l'issue de la partie.
+    if !options.verbose {
+        display::print_plain_board(&game);
+    }
+    let (status, code) = outcome(&mut game);
+    println!("\nGame {} after {} move(s) (seed {}).", status, game.moves, seed);
+    ExitCode::from(code)
+}
+
+/*
+ *  Tests des parties scriptées
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Cell;
+
+    // partie d'essai dont le premier coup est joué en haut à gauche.
+    fn started() -> Minesweeper {
+        let mut game = Minesweeper::with_seed(BoardInfo::classic(8, 10), 3);
+        play(&mut game, "0,0").unwrap();
+        game
+    }
+
+    fn play(game: &mut Minesweeper, script: &str) -> Result<(), (usize, String)> {
+        run_lines(game, script.lines().map(|line| Ok(line.to_string())), false)
+    }
+
+    fn squares(game: &Minesweeper, hidden: fn(Cell) -> bool) -> Vec<String> {
+        (0..8).flat_map(|row| (0..8).map(move |col| (row, col)))
+            .filter(|&(row, col)| hidden(game.cell(row, col)))
+            .map(|(row, col)| format!("{},{}", row, col))
+            .collect()
+    }
+
+    #[test]
+    fn revealing_every_safe_square_wins() {
+        let mut game = started();
+        let safe = squares(&game, |cell| cell == Cell::Undiscovered);
+        assert!(!safe.is_empty());
+        play(&mut game, &safe.join("\n")).unwrap();
+        assert_eq!(outcome(&mut game).1, EXIT_WON);
+    }
+
+    #[test]
+    fn revealing_a_mine_loses_and_stops_the_script() {
+        let mut game = started();
+        let mines = squares(&game, |cell| matches!(cell, Cell::Mine(_)));
+        // la ligne invalide après la mine n'est jamais lue.
+        play(&mut game, &format!("# première mine\n{}\nnot a move", mines[0])).unwrap();
+        assert_eq!(outcome(&mut game).1, EXIT_LOST);
+    }
+
+    #[test]
+    fn invalid_lines_are_reported_with_their_number() {
+        let mut game = started();
+        assert_eq!(play(&mut game, "\n1,1!\n8,0").unwrap_err().0, 3);
+        assert_eq!(play(&mut game, "hello").unwrap_err().0, 1);
+    }
+
+    #[test]
+    fn scripts_ending_early_leave_the_game_unfinished() {
+        let mut game = started();
+        assert_eq!(outcome(&mut game).1, EXIT_UNFINISHED);
+        play(&mut game, "1,1!\nquit\n2,2").unwrap();
+        assert_eq!(outcome(&mut game).1, EXIT_UNFINISHED);
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ *      Nom du fichier : simulation.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_derive::Serialize;
+
+use crate::game::{BoardInfo, GameActions, GameMode, Minesweeper};
+use super::autoplay;
+
+/*
+ *  Définition des constantes
+ */
+// nombre de cases au-delà duquel un mode n'est simulé que s'il est demandé (avec '--mode').
+const MAX_DEFAULT_CELLS: usize = 100 * 100;
+
+/*
+ *  Définition des Structures
+ */
+// Paramètres d'une simulation en lot.
+pub(crate) struct SimulationOptions {
+    pub(crate) games: usize,
+    pub(crate) seed: u64,
+    pub(crate) threads: usize,
+    pub(crate) modes: Vec<u32>,
+    pub(crate) json: bool,
+}
+
+// Résultat d'une partie simulée.
+struct GameSample {
+    won: bool,
+    guesses: usize,
+    three_bv: usize,
+    generation: Duration,
+}
+
+// Répartition du 3BV des plateaux générés.
+#[derive(Serialize)]
+struct ThreeBvDistribution {
+    min: usize,
+    median: usize,
+    mean: f64,
+    max: usize,
+    histogram: BTreeMap<usize, usize>,
+}
+
+// Statistiques d'un mode de jeu.
+#[derive(Serialize)]
+struct ModeReport {
+    name: String,
+    index: u32,
+    games: usize,
+    wins: usize,
+    win_rate: f64,
+    average_guesses: f64,
+    three_bv: ThreeBvDistribution,
+    average_generation_ms: f64,
+}
+
+/*
+ *  Fonction qui joue une partie avec le robot, sans aucun affichage
+ */
+fn simulate_game(board_info: &BoardInfo, seed: u64) -> GameSample {
+    let mut game = Minesweeper::with_seed(board_info.clone(), seed);
+
+    // on génère le plateau au centre, comme le robot, en mesurant le temps de génération.
+    let (row, col) = game.grid.center();
+    let start = Instant::now();
+    game.initialize_game_on_first_play(row, col);
+    let generation = start.elapsed();
+    let three_bv = game.three_bv();
+
+    // on laisse le robot terminer la partie sans délai (donc sans affichage).
+    let report = autoplay::finish_game(&mut game, None);
+    GameSample { won: report.won, guesses: report.guesses, three_bv, generation }
+}
+
+/*
+ *  Fonction qui répartit les parties d'un mode sur plusieurs fils d'exécution
+ */
+fn simulate_mode(board_info: &BoardInfo, options: &SimulationOptions) -> Vec<GameSample> {
+    let threads = options.threads.clamp(1, options.games.max(1));
+
+    thread::scope(|scope| {
+        // chaque fil joue une partie sur `threads`, avec la graine de base décalée de l'indice de la partie.
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_index| {
+                scope.spawn(move || {
+                    (thread_index..options.games)
+                        .step_by(threads)
+                        .map(|game_index| simulate_game(board_info, options.seed.wrapping_add(game_index as u64)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles.into_iter().flat_map(|handle| handle.join().expect("simulation thread panicked")).collect()
+    })
+}
+
+/*
+ *  Fonction qui calcule les statistiques d'un mode à partir des parties jouées
+ */
+fn summarize(mode: &GameMode, samples: &[GameSample]) -> ModeReport {
+    let games = samples.len();
+    let wins = samples.iter().filter(|sample| sample.won).count();
+    let ratio = |total: f64| if games == 0 { 0.0 } else { total / games as f64 };
+
+    // on construit la distribution du 3BV.
+    let mut values: Vec<usize> = samples.iter().map(|sample| sample.three_bv).collect();
+    values.sort_unstable();
+    let mut histogram = BTreeMap::new();
+    for &value in &values {
+        *histogram.entry(value).or_insert(0) += 1;
+    }
+
+    ModeReport {
+        name: mode.name.clone(),
+        index: mode.index,
+        games,
+        wins,
+        win_rate: ratio(wins as f64),
+        average_guesses: ratio(samples.iter().map(|sample| sample.guesses as f64).sum()),
+        three_bv: ThreeBvDistribution {
+            min: values.first().copied().unwrap_or(0),
+            median: values.get(games / 2).copied().unwrap_or(0),
+            mean: ratio(values.iter().sum::<usize>() as f64),
+            max: values.last().copied().unwrap_or(0),
+            histogram,
+        },
+        average_generation_ms: ratio(samples.iter().map(|sample| sample.generation.as_secs_f64() * 1000.0).sum()),
+    }
+}
+
+/*
+ *  Fonction qui affiche les statistiques sous forme de tableau
+ */
+fn print_table(reports: &[ModeReport]) {
+    println!(
+        "{:<12} {:>7} {:>9} {:>12} {:>20} {:>15}",
+        "Mode", "Games", "Win rate", "Avg guesses", "3BV min/med/avg/max", "Avg gen (ms)"
+    );
+    for report in reports {
+        let three_bv = format!(
+            "{}/{}/{:.1}/{}",
+            report.three_bv.min, report.three_bv.median, report.three_bv.mean, report.three_bv.max
+        );
+        println!(
+            "{:<12} {:>7} {:>8.1}% {:>12.2} {:>20} {:>15.3}",
+            report.name, report.games, report.win_rate * 100.0, report.average_guesses, three_bv, report.average_generation_ms
+        );
+    }
+}
+
+/*
+ *  Fonction principale de la simulation en lot
+ */
+pub(crate) fn run(modes: &[GameMode], options: &SimulationOptions) -> Result<(), String> {
+    // on sélectionne les modes demandés (tous par défaut, sauf les très grands plateaux, bien trop longs à simuler).
+    // le mode infini n'a pas de fin de partie gagnante : il est exclu des simulations.
+    let selected: Vec<&GameMode> = if options.modes.is_empty() {
+        modes.iter().filter(|mode| !mode.endless).filter(|mode| {
+            let cells = (mode.board_size as usize).pow(2) * (mode.depth as usize).max(1);
+            if cells > MAX_DEFAULT_CELLS {
+                eprintln!("Skipping the mode {} ({}, {} squares) : simulate it with '--mode {}'.", mode.index, mode.name, cells, mode.index);
+            }
+            cells <= MAX_DEFAULT_CELLS
+        }).collect()
+    } else {
+        options.modes.iter().map(|&index| {
+            match modes.iter().find(|mode| mode.index == index) {
+                Some(mode) if mode.endless => Err(format!("The endless mode {} cannot be simulated.", index)),
+                Some(mode) => Ok(mode),
+                None => Err(format!("Unknown game mode {}.", index)),
+            }
+        }).collect::<Result<_, _>>()?
+    };
+
+    // on simule chaque mode puis on calcule ses statistiques.
+    let reports: Vec<ModeReport> = selected
+        .into_iter()
+        .map(|mode| summarize(mode, &simulate_mode(&mode.board_info(), options)))
+        .collect();
+
+    if options.json {
+        let json = serde_json::to_string_pretty(&reports).map_err(|err| err.to_string())?;
+        println!("{}", json);
+    } else {
+        print_table(&reports);
+    }
+    Ok(())
+}
@@ -0,0 +1,552 @@
+/*
+ *      Nom du fichier : solver.rs
+ *
+ *      Créé le : 19 octobre 2026
+ *      Auteur : Théo BENARD & Joshua MONTREUIL
+ *      Projet : Démineur en Rust
+ *      Cours : Programmation Sure et Efficace
+ */
+
+/*
+ *  Import des bibliothèques externes
+ */
+use std::collections::{HashMap, HashSet};
+
+use crate::game::{Cell, Minesweeper};
+use super::grid;
+
+/*
+ *  Définition des constantes du solveur
+ */
+// nombre maximal de cases d'une composante de frontière énumérée exhaustivement.
+const MAX_ENUMERATED_CELLS: usize = 48;
+// nombre maximal de nœuds explorés par énumération avant d'abandonner.
+const MAX_SEARCH_NODES: u64 = 200_000;
+// distance autour des dernières cases révélées prise en compte par l'analyse locale.
+const LOCAL_RADIUS: usize = 2;
+
+/*
+ *  Définition des Structures
+ */
+// Résultat de l'analyse du plateau visible par le joueur.
+pub(crate) struct Analysis {
+    pub(crate) safe: Vec<(usize, usize)>,
+    pub(crate) mines: Vec<(usize, usize)>,
+    pub(crate) best_guess: Option<((usize, usize), f64)>,
+}
+
+// Contrainte donnée par un chiffre : l'un des nombres de mines `counts` (triés) parmi les cases `cells`
+// (un seul nombre pour un chiffre exact, plusieurs pour les variantes qui déforment les chiffres).
+struct Constraint {
+    cells: Vec<usize>,
+    counts: Vec<usize>,
+}
+
+// Composante en cours de construction : cases globales, numérotation locale et contraintes.
+type ComponentBuilder = (Vec<usize>, HashMap<usize, usize>, Vec<Constraint>);
+
+// État de l'énumération des configurations possibles d'une composante.
+// une case peut contenir de 0 à `mines_per_cell` mines : chaque configuration est pondérée par
+// le nombre de façons d'y arriver au tirage (produit des coefficients binomiaux de chaque case).
+struct Enumeration<'a> {
+    constraints: &'a [Constraint],
+    cell_constraints: Vec<Vec<usize>>,
+    mines_per_cell: usize,
+    weights: Vec<f64>,
+    placed: Vec<usize>,
+    left: Vec<usize>,
+    assignment: Vec<usize>,
+    solutions: f64,
+    mine_counts: Vec<f64>,
+    nodes: u64,
+}
+
+/*
+ *  Fonction qui indique si une case est encore inconnue pour le joueur
+ */
+fn is_hidden(game: &Minesweeper, row: usize, col: usize) -> bool {
+    // les drapeaux sont considérés comme inconnus : ceux du joueur peuvent être faux.
+    // les trous du masque ne font pas partie du plateau, et les mines touchées sont connues.
+    !game.grid.has(row, col, grid::REVEALED | grid::MASKED | grid::EXPLODED)
+}
+
+/*
+ *  Fonction qui compte les mines déjà touchées (donc connues du joueur) parmi des cases
+ */
+fn known_mines(game: &Minesweeper, cells: impl Iterator<Item = (usize, usize)>) -> usize {
+    cells.filter(|&(r, c)| game.grid.has(r, c, grid::EXPLODED)).map(|(r, c)| game.grid.mines_at(r, c) as usize).sum()
+}
+
+/*
+ *  Implémentation de l'énumération par retour sur trace
+ */
+impl<'a> Enumeration<'a> {
+    fn new(num_cells: usize, constraints: &'a [Constraint], mines_per_cell: usize) -> Self {
+        // on associe à chaque case la liste des contraintes qui la concernent.
+        let mut cell_constraints = vec![Vec::new(); num_cells];
+        for (k, constraint) in constraints.iter().enumerate() {
+            for &cell in &constraint.cells {
+                cell_constraints[cell].push(k);
+            }
+        }
+
+        // on calcule le nombre de façons de placer `value` mines parmi les `mines_per_cell` places d'une case.
+        let mut weights = vec![1.0; mines_per_cell + 1];
+        for value in 1..=mines_per_cell {
+            weights[value] = weights[value - 1] * (mines_per_cell - value + 1) as f64 / value as f64;
+        }
+
+        Enumeration {
+            constraints,
+            cell_constraints,
+            mines_per_cell,
+            weights,
+            placed: vec![0; constraints.len()],
+            left: constraints.iter().map(|constraint| constraint.cells.len()).collect(),
+            assignment: vec![0; num_cells],
+            solutions: 0.0,
+            mine_counts: vec![0.0; num_cells],
+            nodes: 0,
+        }
+    }
+
+    /*
+     *  Fonction qui explore les configurations à partir de la case `cell` (renvoie faux si abandon)
+     */
+    fn search(&mut self, cell: usize, weight: f64) -> bool {
+        // on limite la taille de la recherche pour garder un temps de réponse raisonnable.
+        self.nodes += 1;
+        if self.nodes > MAX_SEARCH_NODES {
+            return false;
+        }
+
+        // toutes les cases ont une valeur : on comptabilise cette configuration.
+        if cell == self.assignment.len() {
+            self.solutions += weight;
+            for (index, &mines) in self.assignment.iter().enumerate() {
+                if mines > 0 {
+                    self.mine_counts[index] += weight;
+                }
+            }
+            return true;
+        }
+
+        for mines in 0..=self.mines_per_cell {
+            // on vérifie que chaque contrainte reste satisfiable avec cette valeur.
+            let feasible = self.cell_constraints[cell].iter().all(|&k| {
+                let placed = self.placed[k] + mines;
+                let capacity = placed + (self.left[k] - 1) * self.mines_per_cell;
+                self.constraints[k].counts.iter().any(|&target| placed <= target && capacity >= target)
+            });
+            if !feasible {
+                continue;
+            }
+
+            // on applique la valeur, on explore la suite puis on annule.
+            for &k in &self.cell_constraints[cell] {
+                self.placed[k] += mines;
+                self.left[k] -= 1;
+            }
+            self.assignment[cell] = mines;
+            let completed = self.search(cell + 1, weight * self.weights[mines]);
+            for &k in &self.cell_constraints[cell] {
+                self.placed[k] -= mines;
+                self.left[k] += 1;
+            }
+            if !completed {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/*
+ *  Fonction qui construit les contraintes données par les chiffres révélés
+ */
+fn collect_constraints(game: &Minesweeper, clues: impl Iterator<Item = (usize, usize)>) -> Vec<Constraint> {
+    let size = game.grid.size();
+    let mut seen = HashSet::new();
+    let mut constraints = Vec::new();
+
+    for (row, col) in clues {
+        // seules les cases chiffrées (ou vides) donnent une information.
+        let shown = match game.cell(row, col) {
+            Cell::Number(n) => n as usize,
+            Cell::Empty => 0,
+            _ => continue,
+        };
+
+        // on ne garde que les cases comptées par le chiffre encore inconnues.
+        let cells: Vec<usize> = game.grid.clue_area(row, col)
+            .filter(|&(r, c)| is_hidden(game, r, c))
+            .map(|(r, c)| r * size + c)
+            .collect();
+        // les cases révélées ne cachent aucune mine et les mines touchées sont connues :
+        // le chiffre, diminué de ces dernières, ne porte que sur les cases inconnues.
+        let known = known_mines(game, game.grid.clue_area(row, col));
+        let counts: Vec<usize> = game.grid.clue_rule().possible_counts(shown, cells.len() * game.grid.mines_per_cell() + known)
+            .into_iter()
+            .filter_map(|count| count.checked_sub(known))
+            .collect();
+
+        // on ignore les contraintes vides ou déjà connues.
+        if !cells.is_empty() && seen.insert((cells.clone(), counts.clone())) {
+            constraints.push(Constraint { cells, counts });
+        }
+    }
+
+    // on trie les contraintes dans l'ordre du plateau pour que l'énumération progresse de proche en proche.
+    constraints.sort_unstable_by(|a, b| a.cells.cmp(&b.cells));
+    constraints
+}
+
+/*
+ *  Fonction qui construit les contraintes données par le nombre de mines de chaque ligne et colonne
+ */
+fn line_constraints(game: &Minesweeper) -> Vec<Constraint> {
+    let size = game.grid.size();
+    let rows = (0..size).map(|row| (game.grid.row_total(row), (0..size).map(|col| (row, col)).collect::<Vec<_>>()));
+    let cols = (0..size).map(|col| (game.grid.col_total(col), (0..size).map(|row| (row, col)).collect::<Vec<_>>()));
+
+    // les mines d'une ligne qui n'ont pas été touchées sont cachées dans ses cases encore inconnues.
+    rows.chain(cols)
+        .map(|(total, line)| Constraint {
+            counts: vec![total - known_mines(game, line.iter().copied())],
+            cells: line.into_iter().filter(|&(r, c)| is_hidden(game, r, c)).map(|(r, c)| r * size + c).collect(),
+        })
+        .filter(|constraint| !constraint.cells.is_empty())
+        .collect()
+}
+
+/*
+ *  Fonction qui regroupe les contraintes en composantes indépendantes
+ */
+fn split_components(constraints: Vec<Constraint>) -> Vec<(Vec<usize>, Vec<Constraint>)> {
+    // on relie les contraintes qui partagent au moins une case (union-find).
+    let mut parent: Vec<usize> = (0..constraints.len()).collect();
+    fn find(parent: &mut [usize], k: usize) -> usize {
+        let mut root = k;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[k] = root;
+        root
+    }
+
+    let mut owner: HashMap<usize, usize> = HashMap::new();
+    for (k, constraint) in constraints.iter().enumerate() {
+        for &cell in &constraint.cells {
+            match owner.get(&cell) {
+                Some(&other) => {
+                    let (a, b) = (find(&mut parent, k), find(&mut parent, other));
+                    parent[a] = b;
+                }
+                None => {
+                    owner.insert(cell, k);
+                }
+            }
+        }
+    }
+
+    // on répartit les contraintes par composante en numérotant les cases localement.
+    let mut groups: HashMap<usize, ComponentBuilder> = HashMap::new();
+    for (k, constraint) in constraints.into_iter().enumerate() {
+        let root = find(&mut parent, k);
+        let (cells, local, group) = groups.entry(root).or_default();
+        let local_cells = constraint.cells.iter().map(|&cell| {
+            *local.entry(cell).or_insert_with(|| {
+                cells.push(cell);
+                cells.len() - 1
+            })
+        }).collect();
+        group.push(Constraint { cells: local_cells, counts: constraint.counts });
+    }
+
+    groups.into_values().map(|(cells, _, group)| (cells, group)).collect()
+}
+
+/*
+ *  Fonction qui énumère les configurations d'un ensemble de contraintes (None si trop long)
+ */
+fn enumerate(num_cells: usize, constraints: &[Constraint], mines_per_cell: usize) -> Option<Vec<f64>> {
+    let mut enumeration = Enumeration::new(num_cells, constraints, mines_per_cell);
+    if !enumeration.search(0, 1.0) || enumeration.solutions == 0.0 {
+        return None;
+    }
+    let total = enumeration.solutions;
+    Some(enumeration.mine_counts.iter().map(|&count| count / total).collect())
+}
+
+/*
+ *  Fonction qui raisonne localement : chaque contrainte avec celles qui la chevauchent
+ */
+fn local_probabilities(num_cells: usize, constraints: &[Constraint], mines_per_cell: usize) -> Vec<f64> {
+    let mut cell_constraints = vec![Vec::new(); num_cells];
+    for (k, constraint) in constraints.iter().enumerate() {
+        for &cell in &constraint.cells {
+            cell_constraints[cell].push(k);
+        }
+    }
+
+    let mut forced: Vec<Option<f64>> = vec![None; num_cells];
+    let mut estimates = vec![(0.0, 0usize); num_cells];
+    for constraint in constraints {
+        // on rassemble la contrainte et ses voisines, les plus petites d'abord, en renumérotant leurs cases.
+        let mut neighbourhood: Vec<usize> = constraint.cells.iter().flat_map(|&cell| cell_constraints[cell].iter().copied()).collect();
+        neighbourhood.sort_unstable();
+        neighbourhood.dedup();
+        neighbourhood.sort_by_key(|&other| (constraints[other].cells != constraint.cells, constraints[other].cells.len()));
+        let mut cells: Vec<usize> = Vec::new();
+        let mut local: HashMap<usize, usize> = HashMap::new();
+        let mut sub: Vec<Constraint> = Vec::new();
+        for &other in &neighbourhood {
+            // on laisse de côté les voisines qui agrandiraient trop le sous-problème (ce qui reste une relaxation).
+            let new_cells = constraints[other].cells.iter().filter(|cell| !local.contains_key(cell)).count();
+            if !sub.is_empty() && cells.len() + new_cells > MAX_ENUMERATED_CELLS {
+                continue;
+            }
+            sub.push(Constraint {
+                cells: constraints[other].cells.iter().map(|&cell| {
+                    *local.entry(cell).or_insert_with(|| {
+                        cells.push(cell);
+                        cells.len() - 1
+                    })
+                }).collect(),
+                counts: constraints[other].counts.clone(),
+            });
+        }
+
+        // une case certaine dans ce sous-problème l'est aussi dans la composante entière.
+        if let Some(probabilities) = enumerate(cells.len(), &sub, mines_per_cell) {
+            for (index, &cell) in cells.iter().enumerate() {
+                let probability = probabilities[index];
+                if probability == 0.0 || probability == 1.0 {
+                    forced[cell] = Some(probability);
+                } else if constraint.cells.contains(&cell) {
+                    estimates[cell].0 += probability;
+                    estimates[cell].1 += 1;
+                }
+            }
+        }
+    }
+
+    (0..num_cells).map(|cell| match (forced[cell], estimates[cell]) {
+        (Some(probability), _) => probability,
+        (None, (total, count)) if count > 0 => total / count as f64,
+        // sans estimation, on se rabat sur la densité (moyenne) des contraintes de la case.
+        (None, _) => cell_constraints[cell].iter()
+            .map(|&k| {
+                let counts = &constraints[k].counts;
+                counts.iter().sum::<usize>() as f64 / counts.len().max(1) as f64 / constraints[k].cells.len() as f64
+            })
+            .fold(0.0, f64::max),
+    }).collect()
+}
+
+/*
+ *  Fonction qui estime la probabilité de mine des cases d'une composante
+ */
+fn component_probabilities(num_cells: usize, constraints: &[Constraint], mines_per_cell: usize) -> (Vec<f64>, bool) {
+    // on énumère toutes les configurations si la composante est assez petite.
+    if num_cells <= MAX_ENUMERATED_CELLS {
+        if let Some(probabilities) = enumerate(num_cells, constraints, mines_per_cell) {
+            return (probabilities, true);
+        }
+    }
+
+    // sinon, on raisonne localement autour de chaque contrainte.
+    (local_probabilities(num_cells, constraints, mines_per_cell), false)
+}
+
+/*
+ *  Fonction qui cherche des coups sûrs en ne regardant que les alentours de quelques cases
+ *  (beaucoup plus rapide que l'analyse complète sur les grands plateaux)
+ */
+pub(crate) fn analyse_around(game: &Minesweeper, cells: &[(usize, usize)]) -> Analysis {
+    let size = game.grid.size();
+    let mut analysis = Analysis { safe: Vec::new(), mines: Vec::new(), best_guess: None };
+
+    // on ne retient que les chiffres proches des cases données (à au plus `LOCAL_RADIUS` pas de voisinage).
+    let mut clues: HashSet<(usize, usize)> = cells.iter().copied().collect();
+    let mut frontier = cells.to_vec();
+    for _ in 0..LOCAL_RADIUS {
+        frontier = frontier.iter()
+            .flat_map(|&(row, col)| game.grid.neighbours(row, col))
+            .filter(|&cell| clues.insert(cell))
+            .collect();
+    }
+    let mut clues: Vec<(usize, usize)> = clues.into_iter().collect();
+    clues.sort_unstable();
+
+    // une partie des contraintes est une relaxation du problème : ce qui y est certain l'est aussi globalement.
+    for (cells, constraints) in split_components(collect_constraints(game, clues.into_iter())) {
+        let (probabilities, _) = component_probabilities(cells.len(), &constraints, game.grid.mines_per_cell());
+        for (local, &cell) in cells.iter().enumerate() {
+            if probabilities[local] == 0.0 {
+                analysis.safe.push((cell / size, cell % size));
+            } else if probabilities[local] == 1.0 {
+                analysis.mines.push((cell / size, cell % size));
+            }
+        }
+    }
+    analysis.safe.sort_unstable();
+    analysis.mines.sort_unstable();
+    analysis
+}
+
+/*
+ *  Fonction qui analyse le plateau visible et en déduit les coups sûrs
+ */
+pub(crate) fn analyse(game: &Minesweeper) -> Analysis {
+    let size = game.grid.size();
+    let mut analysis = Analysis { safe: Vec::new(), mines: Vec::new(), best_guess: None };
+
+    // on calcule la probabilité de mine de chaque case de la frontière.
+    let mut frontier: HashMap<usize, f64> = HashMap::new();
+    let mut frontier_exact = true;
+    let clues = (0..game.grid.cell_count()).map(|cell| (cell / size, cell % size));
+    let mut constraints = collect_constraints(game, clues);
+    // les totaux des lignes et des colonnes s'ajoutent aux chiffres (ils couvrent alors toutes les cases inconnues).
+    if game.configuration.line_totals {
+        constraints.extend(line_constraints(game));
+    }
+    for (cells, constraints) in split_components(constraints) {
+        let (probabilities, exact) = component_probabilities(cells.len(), &constraints, game.grid.mines_per_cell());
+        frontier_exact &= exact;
+        for (local, &cell) in cells.iter().enumerate() {
+            frontier.insert(cell, probabilities[local]);
+        }
+    }
+
+    // on estime la densité de mines parmi les cases inconnues hors frontière.
+    let interior: Vec<usize> = (0..game.grid.cell_count())
+        .filter(|&cell| is_hidden(game, cell / size, cell % size) && !frontier.contains_key(&cell))
+        .collect();
+    // les mines déjà touchées comptent avec celles de la frontière.
+    let exploded = known_mines(game, (0..game.grid.cell_count()).map(|cell| (cell / size, cell % size)));
+    let expected_frontier_mines = frontier.values().sum::<f64>() + exploded as f64;
+    let interior_probability = if interior.is_empty() {
+        1.0
+    } else {
+        ((game.configuration.num_mines as f64 - expected_frontier_mines) / interior.len() as f64).clamp(0.0, 1.0)
+    };
+
+    // on classe les cases dont l'état est certain.
+    for (&cell, &probability) in &frontier {
+        if probability == 0.0 {
+            analysis.safe.push((cell / size, cell % size));
+        } else if probability == 1.0 {
+            analysis.mines.push((cell / size, cell % size));
+        }
+    }
+    // si la frontière est entièrement résolue, le nombre de mines restant tranche pour l'intérieur
+    // (avec plusieurs mines par case, les cases minées de la frontière en cachent au moins une chacune :
+    // seul un reste nul est alors certain).
+    let frontier_solved = frontier_exact && frontier.values().all(|&p| p == 0.0 || p == 1.0);
+    if frontier_solved && !interior.is_empty() {
+        let remaining = game.configuration.num_mines as f64 - expected_frontier_mines;
+        if remaining == 0.0 {
+            analysis.safe.extend(interior.iter().map(|&cell| (cell / size, cell % size)));
+        } else if remaining == interior.len() as f64 && game.grid.mines_per_cell() == 1 {
+            analysis.mines.extend(interior.iter().map(|&cell| (cell / size, cell % size)));
+        }
+    }
+    analysis.safe.sort_unstable();
+    analysis.mines.sort_unstable();
+
+    // on cherche la case la moins risquée parmi la frontière.
+    for (&cell, &probability) in &frontier {
+        let candidate = ((cell / size, cell % size), probability);
+        if analysis.best_guess.is_none_or(|(best, p)| probability < p || (probability == p && candidate.0 < best)) {
+            analysis.best_guess = Some(candidate);
+        }
+    }
+    // on compare avec la case intérieure ayant le moins de voisins (coins et bords d'abord).
+    if let Some(&cell) = interior.iter().min_by_key(|&&cell| game.grid.neighbours(cell / size, cell % size).count()) {
+        if analysis.best_guess.is_none_or(|(_, p)| interior_probability < p) {
+            analysis.best_guess = Some(((cell / size, cell % size), interior_probability));
+        }
+    }
+
+    analysis
+}
+
+/*
+ *  Tests de l'analyse locale, comparée à l'analyse complète du plateau
+ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Action, BoardInfo, GameActions};
+
+    // cases cachées voisines d'une case révélée : celles que les chiffres renseignent.
+    fn on_frontier(game: &Minesweeper, (row, col): (usize, usize)) -> bool {
+        game.grid.neighbours(row, col).any(|(r, c)| game.grid.is_revealed(r, c))
+    }
+
+    // l'analyse complète n'est exacte que si chaque composante a pu être énumérée.
+    fn full_analysis_is_exact(game: &Minesweeper) -> bool {
+        let size = game.grid.size();
+        let clues = (0..game.grid.cell_count()).map(|cell| (cell / size, cell % size));
+        split_components(collect_constraints(game, clues))
+            .iter()
+            .all(|(cells, constraints)| component_probabilities(cells.len(), constraints, game.grid.mines_per_cell()).1)
+    }
+
+    #[test]
+    fn local_analysis_is_sound_and_agrees_with_the_exact_full_analysis() {
+        let (mut deductions, mut compared) = (0, 0);
+        for seed in 0..30 {
+            let mut game = Minesweeper::with_seed(BoardInfo::classic(16, 40), seed);
+            let mut recent = game.apply_action(Action::Reveal(8, 8));
+            // on avance de quelques coups sûrs, en comparant les deux analyses à chaque fois.
+            for _ in 0..10 {
+                let local = analyse_around(&game, &recent);
+                let full = analyse(&game);
+                // ce que l'analyse locale tient pour certain l'est vraiment.
+                assert!(local.safe.iter().all(|&(row, col)| game.grid.mines_at(row, col) == 0), "seed {}", seed);
+                assert!(local.mines.iter().all(|&(row, col)| game.grid.mines_at(row, col) > 0), "seed {}", seed);
+                deductions += local.safe.len() + local.mines.len();
+                // et l'analyse complète le trouve aussi, quand elle est exacte (sinon l'analyse locale peut en trouver plus).
+                if full_analysis_is_exact(&game) {
+                    assert!(local.safe.iter().all(|cell| full.safe.contains(cell)), "seed {}", seed);
+                    assert!(local.mines.iter().all(|cell| full.mines.contains(cell)), "seed {}", seed);
+                    compared += 1;
+                }
+
+                let Some(&cell) = full.safe.first() else { break };
+                recent = game.apply_action(Action::Reveal(cell.0, cell.1));
+                if game.game_over || game.check_win() {
+                    break;
+                }
+            }
+        }
+        // les parties jouées donnent bien des déductions locales à comparer.
+        assert!(deductions > 0 && compared > 0);
+    }
+
+    #[test]
+    fn local_analysis_of_every_cell_matches_the_full_analysis_on_the_frontier() {
+        let mut compared = 0;
+        for seed in 0..30 {
+            let mut game = Minesweeper::with_seed(BoardInfo::classic(12, 20), seed);
+            game.apply_action(Action::Reveal(6, 6));
+            if !full_analysis_is_exact(&game) {
+                continue;
+            }
+            let every_cell: Vec<(usize, usize)> = (0..12).flat_map(|row| (0..12).map(move |col| (row, col))).collect();
+
+            let local = analyse_around(&game, &every_cell);
+            let full = analyse(&game);
+            // l'analyse complète déduit aussi l'intérieur du nombre de mines restant : on ne compare que la frontière.
+            let frontier = |cells: Vec<(usize, usize)>| -> Vec<(usize, usize)> {
+                cells.into_iter().filter(|&cell| on_frontier(&game, cell)).collect()
+            };
+            assert_eq!(local.safe, frontier(full.safe), "seed {}", seed);
+            assert_eq!(local.mines, frontier(full.mines), "seed {}", seed);
+            compared += 1;
+        }
+        assert!(compared > 0);
+    }
+}
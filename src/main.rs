@@ -1,5 +1,7 @@
 mod game;
 
-fn main() {
-    game::main_game();
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    game::main_game()
 }
\ No newline at end of file